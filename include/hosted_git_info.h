@@ -0,0 +1,165 @@
+#ifndef HOSTED_GIT_INFO_H
+#define HOSTED_GIT_INFO_H
+
+/* This file is generated by cbindgen. Do not edit it manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The representations that can be rendered with [hgi_render].
+typedef enum HgiFormat {
+  // See [HostedGitInfo::ssh].
+  HGI_FORMAT_SSH = 0,
+  // See [HostedGitInfo::sshurl].
+  HGI_FORMAT_SSH_URL = 1,
+  // See [HostedGitInfo::https].
+  HGI_FORMAT_HTTPS = 2,
+  // See [HostedGitInfo::git].
+  HGI_FORMAT_GIT = 3,
+  // See [HostedGitInfo::shortcut].
+  HGI_FORMAT_SHORTCUT = 4,
+  // See [HostedGitInfo::path].
+  HGI_FORMAT_PATH = 5,
+  // See [HostedGitInfo::browse] and [HostedGitInfo::browse_file].
+  HGI_FORMAT_BROWSE = 6,
+  // See [HostedGitInfo::docs].
+  HGI_FORMAT_DOCS = 7,
+  // See [HostedGitInfo::bugs].
+  HGI_FORMAT_BUGS = 8,
+  // See [HostedGitInfo::tarball].
+  HGI_FORMAT_TARBALL = 9,
+  // See [HostedGitInfo::file].
+  HGI_FORMAT_FILE = 10,
+  // The default representation, without credentials.
+  HGI_FORMAT_DEFAULT = 11,
+} HgiFormat;
+
+// See [Provider].
+typedef enum HgiProvider {
+  HGI_PROVIDER_BITBUCKET = 0,
+  HGI_PROVIDER_GIST = 1,
+  HGI_PROVIDER_GITHUB = 2,
+  HGI_PROVIDER_GITLAB = 3,
+} HgiProvider;
+
+// The status codes that are returned by the fallible functions.
+typedef enum HgiStatus {
+  HGI_STATUS_OK = 0,
+  // A required pointer argument was `NULL`.
+  HGI_STATUS_NULL_POINTER = 1,
+  // A string argument or result was not valid UTF-8, or contained a NUL byte.
+  HGI_STATUS_INVALID_STRING = 2,
+  // See [ParseError::InvalidUrl].
+  HGI_STATUS_INVALID_URL = 3,
+  // See [ParseError::InvalidUriEncoding].
+  HGI_STATUS_INVALID_URI_ENCODING = 4,
+  // See [ParseError::UnknownUrl].
+  HGI_STATUS_UNKNOWN_URL = 5,
+  // The provider does not support the requested format.
+  HGI_STATUS_UNSUPPORTED_FORMAT = 6,
+  // See [ParseError::RequiresLeniency].
+  HGI_STATUS_REQUIRES_LENIENCY = 7,
+  // The format is not one of the [HgiFormat] values.
+  HGI_STATUS_UNKNOWN_FORMAT = 8,
+} HgiStatus;
+
+// An opaque handle to a parsed [HostedGitInfo].
+typedef struct HgiInfo HgiInfo;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Parses a NUL-terminated URL and stores a new handle in `out`.
+//
+// The handle must be released with [hgi_info_free].
+//
+// # Safety
+//
+// `url` must be `NULL` or a valid NUL-terminated string, and `out` must be
+// `NULL` or valid for writes.
+enum HgiStatus hgi_parse(const char *url, struct HgiInfo **out);
+
+// Releases a handle that was created by [hgi_parse].
+//
+// # Safety
+//
+// `info` must be `NULL` or a handle returned by [hgi_parse] that has not
+// been released yet.
+void hgi_info_free(struct HgiInfo *info);
+
+// Stores the provider of the URL in `out`.
+//
+// # Safety
+//
+// `info` must be `NULL` or a valid handle returned by [hgi_parse], and `out`
+// must be `NULL` or valid for writes.
+enum HgiStatus hgi_provider(const struct HgiInfo *info, enum HgiProvider *out);
+
+// Returns the user or organization name, or `NULL` if there is none.
+//
+// # Safety
+//
+// `info` must be `NULL` or a valid handle returned by [hgi_parse].
+char *hgi_user(const struct HgiInfo *info);
+
+// Returns the project name.
+//
+// # Safety
+//
+// `info` must be `NULL` or a valid handle returned by [hgi_parse].
+char *hgi_project(const struct HgiInfo *info);
+
+// Returns the branch, tag or commit, or `NULL` if there is none.
+//
+// # Safety
+//
+// `info` must be `NULL` or a valid handle returned by [hgi_parse].
+char *hgi_committish(const struct HgiInfo *info);
+
+// Returns the subdirectory of the repository, or `NULL` if there is none.
+//
+// # Safety
+//
+// `info` must be `NULL` or a valid handle returned by [hgi_parse].
+char *hgi_directory(const struct HgiInfo *info);
+
+// Returns the unredacted authentication part, or `NULL` if there is none.
+//
+// # Safety
+//
+// `info` must be `NULL` or a valid handle returned by [hgi_parse].
+char *hgi_auth(const struct HgiInfo *info);
+
+// Renders the URL in the given format and stores the new string in `out`.
+//
+// `format` is one of the [HgiFormat] values. It is passed as an `int`,
+// because values outside of the enum would be undefined behavior, and
+// result in [HgiStatus::UnknownFormat] instead.
+//
+// `path` is the file path for [HgiFormat::Browse] and [HgiFormat::File],
+// and may be `NULL`.
+//
+// # Safety
+//
+// `info` must be a valid handle returned by [hgi_parse], `path` must be
+// `NULL` or a valid NUL-terminated string, and `out` must be `NULL` or valid
+// for writes.
+enum HgiStatus hgi_render(const struct HgiInfo *info, int format, const char *path, char **out);
+
+// Releases a string that was returned by one of the other functions, and
+// overwrites it with zeros, because it may contain credentials.
+//
+// # Safety
+//
+// `string` must be `NULL` or a string returned by this library that has
+// not been released yet.
+void hgi_string_free(char *string);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* HOSTED_GIT_INFO_H */
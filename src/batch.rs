@@ -0,0 +1,197 @@
+use crate::{detect_provider, HostedGitInfo, ParseError, ParseErrorKind, Provider};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Parses all inputs with [HostedGitInfo::from_url] and returns the results
+/// in the same order, together with a [BatchReport] of the failures.
+///
+/// ```
+/// use hosted_git_info::{parse_batch, ParseErrorKind, Provider};
+///
+/// let inputs = ["foo/bar", "https://github.com/foo", "https://example.com/foo/bar"];
+/// let batch = parse_batch(&inputs);
+/// assert!(batch.results()[0].is_ok());
+///
+/// let report = batch.report();
+/// assert_eq!(report.failed(), 2);
+/// assert_eq!(report.groups()[0].kind(), ParseErrorKind::UnknownUrl);
+/// assert_eq!(report.groups()[0].provider(), None);
+/// assert_eq!(report.groups()[1].provider(), Some(Provider::GitHub));
+/// ```
+pub fn parse_batch<I, S>(inputs: I) -> Batch
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut results = Vec::new();
+    let mut report = ReportBuilder::default();
+    for (index, input) in inputs.into_iter().enumerate() {
+        let input = input.as_ref();
+        let result = HostedGitInfo::from_url(input);
+        if let Err(error) = &result {
+            report.add_failure(index, input, error);
+        }
+        results.push(result);
+    }
+
+    report.build(results)
+}
+
+/// Like [parse_batch], but parses the inputs in parallel using [rayon].
+///
+/// The results are still returned in the same order as the inputs.
+#[cfg(feature = "rayon")]
+pub fn parse_batch_parallel<S>(inputs: &[S]) -> Batch
+where
+    S: AsRef<str> + Sync,
+{
+    use rayon::prelude::*;
+
+    let results: Vec<_> = inputs
+        .par_iter()
+        .map(|input| HostedGitInfo::from_url(input.as_ref()))
+        .collect();
+
+    let mut report = ReportBuilder::default();
+    for (index, (input, result)) in inputs.iter().zip(&results).enumerate() {
+        if let Err(error) = result {
+            report.add_failure(index, input.as_ref(), error);
+        }
+    }
+
+    report.build(results)
+}
+
+/// The results of [parse_batch].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Batch {
+    results: Vec<Result<HostedGitInfo, ParseError>>,
+    report: BatchReport,
+}
+
+impl Batch {
+    /// The results, in the same order as the inputs.
+    pub fn results(&self) -> &[Result<HostedGitInfo, ParseError>] {
+        &self.results
+    }
+
+    /// Returns the results, in the same order as the inputs.
+    pub fn into_results(self) -> Vec<Result<HostedGitInfo, ParseError>> {
+        self.results
+    }
+
+    /// The aggregated failures.
+    pub fn report(&self) -> &BatchReport {
+        &self.report
+    }
+}
+
+/// The failures of a batch, grouped by [ParseErrorKind] and [Provider].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct BatchReport {
+    total: usize,
+    groups: Vec<FailureGroup>,
+}
+
+impl BatchReport {
+    /// The number of inputs.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// The number of inputs that were parsed successfully.
+    pub fn succeeded(&self) -> usize {
+        self.total - self.failed()
+    }
+
+    /// The number of inputs that failed to parse.
+    pub fn failed(&self) -> usize {
+        self.groups.iter().map(|group| group.indices.len()).sum()
+    }
+
+    /// The groups of failures, sorted by [ParseErrorKind] and [Provider].
+    pub fn groups(&self) -> &[FailureGroup] {
+        &self.groups
+    }
+}
+
+impl fmt::Display for BatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} inputs failed to parse",
+            self.failed(),
+            self.total
+        )?;
+        for group in &self.groups {
+            let provider = group
+                .provider
+                .map_or("unknown provider", Provider::shortcut);
+            write!(
+                f,
+                "\n  {:?} ({}): {}",
+                group.kind,
+                provider,
+                group.indices.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Failures of a batch with the same [ParseErrorKind] and [Provider].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct FailureGroup {
+    kind: ParseErrorKind,
+    provider: Option<Provider>,
+    indices: Vec<usize>,
+}
+
+impl FailureGroup {
+    /// The kind of the errors in this group.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// The provider that the inputs seem to belong to, if it could be
+    /// detected before parsing failed.
+    pub fn provider(&self) -> Option<Provider> {
+        self.provider
+    }
+
+    /// The indices of the failed inputs, in ascending order.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+}
+
+#[derive(Default)]
+struct ReportBuilder {
+    groups: BTreeMap<(ParseErrorKind, Option<Provider>), Vec<usize>>,
+}
+
+impl ReportBuilder {
+    fn add_failure(&mut self, index: usize, input: &str, error: &ParseError) {
+        let key = (error.kind(), detect_provider(input));
+        self.groups.entry(key).or_default().push(index);
+    }
+
+    fn build(self, results: Vec<Result<HostedGitInfo, ParseError>>) -> Batch {
+        let groups = self
+            .groups
+            .into_iter()
+            .map(|((kind, provider), indices)| FailureGroup {
+                kind,
+                provider,
+                indices,
+            })
+            .collect();
+
+        let report = BatchReport {
+            total: results.len(),
+            groups,
+        };
+
+        Batch { results, report }
+    }
+}
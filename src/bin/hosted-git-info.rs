@@ -0,0 +1,317 @@
+//! Command-line interface to the `hosted-git-info` crate.
+//!
+//! Run `hosted-git-info --help` for usage information.
+
+use hosted_git_info::{redact_credentials, HostedGitInfo};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::env;
+use std::io::{self, BufRead};
+use std::process;
+
+const USAGE: &str = "\
+Usage:
+    hosted-git-info parse [--json] <URL>...
+    hosted-git-info convert <FORMAT> [--path <PATH>] <URL>...
+    hosted-git-info batch [--json | --to <FORMAT> [--path <PATH>]]
+
+Commands:
+    parse      Prints the fields of the parsed URLs
+    convert    Converts the URLs to another representation
+    batch      Parses the URLs read from stdin, one per line
+
+Formats:
+    ssh, sshurl, https, git, shortcut, path, browse, docs, bugs, tarball, file
+
+Options:
+    --json          Prints the fields as JSON, one object per line
+    --to <FORMAT>   Converts the URLs instead of printing their fields
+    --path <PATH>   The file path for the `browse` format, required for `file`
+    -h, --help      Prints this help message
+
+The exit code is 1 if any URL could not be parsed or converted, and 2 for
+invalid arguments.";
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Format {
+    Ssh,
+    SshUrl,
+    Https,
+    Git,
+    Shortcut,
+    Path,
+    Browse,
+    Docs,
+    Bugs,
+    Tarball,
+    File,
+}
+
+impl Format {
+    fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "ssh" => Some(Format::Ssh),
+            "sshurl" => Some(Format::SshUrl),
+            "https" => Some(Format::Https),
+            "git" => Some(Format::Git),
+            "shortcut" => Some(Format::Shortcut),
+            "path" => Some(Format::Path),
+            "browse" => Some(Format::Browse),
+            "docs" => Some(Format::Docs),
+            "bugs" => Some(Format::Bugs),
+            "tarball" => Some(Format::Tarball),
+            "file" => Some(Format::File),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Output {
+    Text,
+    Json,
+    Convert(Format, Option<String>),
+}
+
+#[derive(Debug)]
+enum Command {
+    Help,
+    Parse(Output, Vec<String>),
+    Batch(Output),
+}
+
+/// The fields that are printed by the `parse` and `batch` commands.
+///
+/// Credentials are redacted, so that they don't end up in logs.
+#[derive(Serialize)]
+struct Fields<'a> {
+    url: Cow<'a, str>,
+    provider: String,
+    user: Option<&'a str>,
+    project: &'a str,
+    committish: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    directory: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filename: Option<&'a str>,
+    auth: Option<String>,
+    default: String,
+}
+
+impl<'a> Fields<'a> {
+    fn new(url: &'a str, info: &'a HostedGitInfo) -> Self {
+        Fields {
+            url: redact_credentials(url),
+            provider: info.provider().to_string(),
+            user: info.user(),
+            project: info.project(),
+            committish: info.committish(),
+            directory: info.directory(),
+            filename: info.filename(),
+            auth: info.credentials().map(|auth| auth.to_string()),
+            default: name(&info.default_representation()),
+        }
+    }
+}
+
+/// Returns the serde name of a unit enum variant, e.g. `shortcut`.
+fn name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let success = match command {
+        Command::Help => {
+            println!("{}", USAGE);
+            true
+        }
+        Command::Parse(output, urls) => {
+            let mut success = true;
+            for url in &urls {
+                success = process_url(url, &output) && success;
+            }
+            success
+        }
+        Command::Batch(output) => {
+            let stdin = io::stdin();
+            let mut success = true;
+            for line in stdin.lock().lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(error) => {
+                        eprintln!("error: Failed to read from stdin: {}", error);
+                        process::exit(1);
+                    }
+                };
+
+                let url = line.trim();
+                if !url.is_empty() {
+                    success = process_url(url, &output) && success;
+                }
+            }
+            success
+        }
+    };
+
+    if !success {
+        process::exit(1);
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let (command, args) = match args.split_first() {
+        Some((command, args)) => (command.as_str(), args),
+        None => return Err("missing command".to_string()),
+    };
+
+    let mut json = false;
+    let mut format = None;
+    let mut path = None;
+    let mut positional = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--json" => json = true,
+            "--to" => format = Some(expect_value(&mut args, "--to")?),
+            "--path" => path = Some(expect_value(&mut args, "--path")?),
+            "--" => positional.extend(args.by_ref().cloned()),
+            flag if flag.starts_with("--") => return Err(format!("unknown option `{}`", flag)),
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    let output = |format: Option<String>| -> Result<Output, String> {
+        match format {
+            Some(_) if json => Err("`--json` can not be combined with a format".to_string()),
+            Some(format) => match Format::from_name(&format) {
+                Some(Format::File) if path.is_none() => {
+                    Err("the `file` format requires `--path`".to_string())
+                }
+                Some(format) => Ok(Output::Convert(format, path.clone())),
+                None => Err(format!("unknown format `{}`", format)),
+            },
+            None if path.is_some() => Err("`--path` requires a format".to_string()),
+            None if json => Ok(Output::Json),
+            None => Ok(Output::Text),
+        }
+    };
+
+    match command {
+        "-h" | "--help" | "help" => Ok(Command::Help),
+        "parse" if format.is_some() => Err("`parse` does not support `--to`".to_string()),
+        "parse" if positional.is_empty() => Err("missing URL".to_string()),
+        "parse" => Ok(Command::Parse(output(None)?, positional)),
+        "convert" if format.is_some() => Err("`convert` does not support `--to`".to_string()),
+        "convert" if positional.len() < 2 => Err("missing format or URL".to_string()),
+        "convert" => {
+            let format = positional.remove(0);
+            Ok(Command::Parse(output(Some(format))?, positional))
+        }
+        "batch" if !positional.is_empty() => Err("`batch` reads the URLs from stdin".to_string()),
+        "batch" => Ok(Command::Batch(output(format)?)),
+        _ => Err(format!("unknown command `{}`", command)),
+    }
+}
+
+fn expect_value<'a, I: Iterator<Item = &'a String>>(
+    args: &mut I,
+    flag: &str,
+) -> Result<String, String> {
+    args.next()
+        .cloned()
+        .ok_or_else(|| format!("`{}` requires a value", flag))
+}
+
+/// Prints the requested output for a single URL, or an error message to
+/// stderr. Returns `false` if the URL could not be processed.
+///
+/// Credentials in the URL are redacted in all messages.
+fn process_url(url: &str, output: &Output) -> bool {
+    let info = match HostedGitInfo::from_url(url) {
+        Ok(info) => info,
+        Err(error) => {
+            eprint!("{}", error.report(url).render());
+            return false;
+        }
+    };
+
+    match output {
+        Output::Text => {
+            let fields = Fields::new(url, &info);
+            println!("url: {}", fields.url);
+            println!("provider: {}", fields.provider);
+            if let Some(user) = fields.user {
+                println!("user: {}", user);
+            }
+            println!("project: {}", fields.project);
+            if let Some(committish) = fields.committish {
+                println!("committish: {}", committish);
+            }
+            if let Some(directory) = fields.directory {
+                println!("directory: {}", directory);
+            }
+            if let Some(filename) = fields.filename {
+                println!("filename: {}", filename);
+            }
+            if let Some(auth) = &fields.auth {
+                println!("auth: {}", auth);
+            }
+            println!("default: {}", fields.default);
+            println!();
+        }
+        Output::Json => match serde_json::to_string(&Fields::new(url, &info)) {
+            Ok(json) => println!("{}", json),
+            Err(error) => {
+                eprintln!("error: {}: {}", redact_credentials(url), error);
+                return false;
+            }
+        },
+        Output::Convert(format, path) => match convert(&info, *format, path.as_deref()) {
+            Some(converted) => println!("{}", converted),
+            None => {
+                eprintln!(
+                    "error: {}: {} does not support the `git` format",
+                    redact_credentials(url),
+                    info.provider().display_name()
+                );
+                return false;
+            }
+        },
+    }
+
+    true
+}
+
+fn convert(info: &HostedGitInfo, format: Format, path: Option<&str>) -> Option<String> {
+    Some(match format {
+        Format::Ssh => info.ssh(),
+        Format::SshUrl => info.sshurl(),
+        Format::Https => info.https(),
+        Format::Git => return info.git(),
+        Format::Shortcut => info.shortcut(),
+        Format::Path => info.path(),
+        Format::Browse => match path {
+            Some(path) => info.browse_file(path, None),
+            None => info.browse(),
+        },
+        Format::Docs => info.docs(),
+        Format::Bugs => info.bugs(),
+        Format::Tarball => info.tarball(),
+        Format::File => info.file(path?),
+    })
+}
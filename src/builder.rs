@@ -0,0 +1,247 @@
+use crate::{Auth, DefaultRepresentation, HostedGitInfo, Namespace, NamespaceError, Provider};
+use thiserror::Error;
+
+/// Errors that can occur when building a [HostedGitInfo] by hand.
+#[derive(PartialEq, Eq, Clone, Debug, Error)]
+pub enum BuildError {
+    /// A required field was not set.
+    #[error("Missing required field `{0}`")]
+    MissingField(&'static str),
+
+    /// The provider requires a user or organization name, but none was set.
+    #[error("{0} requires a user")]
+    MissingUser(Provider),
+
+    /// The user name is empty or contains characters that the provider does
+    /// not allow.
+    #[error("Invalid user `{user}` for {provider}")]
+    InvalidUser { provider: Provider, user: String },
+
+    /// The GitLab namespace does not follow the rules of
+    /// [Namespace::validate].
+    #[error("Invalid namespace `{user}` for gitlab")]
+    InvalidNamespace {
+        user: String,
+        #[source]
+        source: NamespaceError,
+    },
+
+    /// The project name is empty or contains characters that are not allowed.
+    #[error("Invalid project `{0}`")]
+    InvalidProject(String),
+
+    /// The committish is empty.
+    #[error("Invalid committish `{0}`")]
+    InvalidCommittish(String),
+
+    /// The directory is empty or contains `#`.
+    #[error("Invalid directory `{0}`")]
+    InvalidDirectory(String),
+}
+
+/// Builder for [HostedGitInfo] values.
+///
+/// The setters take and return `&mut Self`, so they can be chained on a
+/// temporary builder, or called one by one on a builder in a variable.
+///
+/// ```
+/// use hosted_git_info::{DefaultRepresentation, HostedGitInfoBuilder, Provider};
+///
+/// let info = HostedGitInfoBuilder::default()
+///     .provider(Provider::GitHub)
+///     .user("foo")
+///     .project("bar")
+///     .repr(DefaultRepresentation::Shortcut)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(info.to_string(), "github:foo/bar");
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct HostedGitInfoBuilder {
+    provider: Option<Provider>,
+    user: Option<String>,
+    auth: Option<Auth>,
+    project: Option<String>,
+    committish: Option<String>,
+    directory: Option<String>,
+    filename: Option<String>,
+    default_representation: Option<DefaultRepresentation>,
+}
+
+impl HostedGitInfoBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the provider. Required.
+    pub fn provider(&mut self, provider: Provider) -> &mut Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Sets the user or organization name.
+    ///
+    /// Required for all providers except [Provider::Gist]. For
+    /// [Provider::GitLab] this may contain nested groups, e.g. `group/subgroup`.
+    pub fn user<S: Into<String>>(&mut self, user: S) -> &mut Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    /// Sets the authentication part, in the format `<USER>[:<PASSWORD>]`.
+    pub fn auth<A: Into<Auth>>(&mut self, auth: A) -> &mut Self {
+        self.auth = Some(auth.into());
+        self
+    }
+
+    /// Sets the project name. Required.
+    pub fn project<S: Into<String>>(&mut self, project: S) -> &mut Self {
+        self.project = Some(project.into());
+        self
+    }
+
+    /// Sets the branch, tag or commit.
+    pub fn committish<S: Into<String>>(&mut self, committish: S) -> &mut Self {
+        self.committish = Some(committish.into());
+        self
+    }
+
+    /// Sets the subdirectory of the repository, e.g. `packages/foo`.
+    /// Leading and trailing slashes are removed.
+    pub fn directory<S: Into<String>>(&mut self, directory: S) -> &mut Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Sets the name of a single gist file.
+    pub fn filename<S: Into<String>>(&mut self, filename: S) -> &mut Self {
+        self.filename = Some(filename.into());
+        self
+    }
+
+    /// Sets the [DefaultRepresentation]. Required.
+    pub fn repr(&mut self, repr: DefaultRepresentation) -> &mut Self {
+        self.default_representation = Some(repr);
+        self
+    }
+
+    /// Validates the fields against the rules of the provider and builds
+    /// the [HostedGitInfo].
+    pub fn build(&self) -> Result<HostedGitInfo, BuildError> {
+        let info = self.build_unchecked()?;
+
+        match &info.user {
+            // GitLab supports nested groups like `group/subgroup/project`
+            Some(user) if info.provider == Provider::GitLab => {
+                if let Err(source) = Namespace::new(user).validate() {
+                    return Err(BuildError::InvalidNamespace {
+                        user: user.clone(),
+                        source,
+                    });
+                }
+            }
+            Some(user) if !is_valid_name(user) => {
+                return Err(BuildError::InvalidUser {
+                    provider: info.provider,
+                    user: user.clone(),
+                })
+            }
+            _ => {}
+        }
+
+        if !is_valid_name(&info.project) {
+            return Err(BuildError::InvalidProject(info.project));
+        }
+
+        if let Some(committish) = &info.committish {
+            if committish.is_empty() {
+                return Err(BuildError::InvalidCommittish(committish.clone()));
+            }
+        }
+
+        let directory = match &info.directory {
+            Some(directory) => {
+                let normalized = directory.trim_matches('/');
+                if normalized.is_empty() || normalized.contains('#') {
+                    return Err(BuildError::InvalidDirectory(directory.clone()));
+                }
+                Some(normalized.to_string())
+            }
+            None => None,
+        };
+
+        Ok(HostedGitInfo { directory, ..info })
+    }
+
+    // only checks that the required fields are set, because the parser
+    // accepts names that `build()` rejects, e.g. `github:a/b/c` results in
+    // the user `a/b`
+    fn build_unchecked(&self) -> Result<HostedGitInfo, BuildError> {
+        let provider = self.provider.ok_or(BuildError::MissingField("provider"))?;
+        let project = self
+            .project
+            .clone()
+            .ok_or(BuildError::MissingField("project"))?;
+        let default_representation = self
+            .default_representation
+            .ok_or(BuildError::MissingField("default_representation"))?;
+
+        if self.user.is_none() && provider != Provider::Gist {
+            return Err(BuildError::MissingUser(provider));
+        }
+
+        Ok(HostedGitInfo {
+            provider,
+            user: self.user.clone(),
+            auth: self.auth.clone(),
+            project,
+            committish: self.committish.clone(),
+            directory: self.directory.clone(),
+            filename: self.filename.clone(),
+            default_representation,
+            origin: None,
+        })
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(|c| c == '/' || c == '#')
+}
+
+/// The serialized fields of a [HostedGitInfo].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Fields {
+    provider: Provider,
+    user: Option<String>,
+    auth: Option<Auth>,
+    project: String,
+    committish: Option<String>,
+    directory: Option<String>,
+    filename: Option<String>,
+    default_representation: DefaultRepresentation,
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for HostedGitInfo {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = Fields::deserialize(deserializer)?;
+
+        // the names are not validated, so that everything that the parser
+        // returns can be deserialized again
+        HostedGitInfoBuilder {
+            provider: Some(fields.provider),
+            user: fields.user,
+            auth: fields.auth,
+            project: Some(fields.project),
+            committish: fields.committish,
+            directory: fields.directory,
+            filename: fields.filename,
+            default_representation: Some(fields.default_representation),
+        }
+        .build_unchecked()
+        .map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,261 @@
+use crate::{HostedGitInfo, ParseError, ParseOptions};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+// const cache = new LRU({ max: 1000 })
+const DEFAULT_CAPACITY: usize = 1000;
+
+/// A [HostedGitInfo::from_url] wrapper that memoises the results of the most
+/// recently parsed URLs, like the LRU cache in the original `index.js`.
+///
+/// Both successful results and [ParseError]s are cached. The parser can be
+/// shared between threads.
+///
+/// ```
+/// use hosted_git_info::CachedParser;
+///
+/// let parser = CachedParser::with_capacity(100);
+/// assert!(parser.parse("github:foo/bar").is_ok());
+/// assert!(parser.parse("github:foo/bar").is_ok());
+/// assert!(parser.parse("https://example.com/foo/bar").is_err());
+///
+/// let stats = parser.stats();
+/// assert_eq!(stats.hits(), 1);
+/// assert_eq!(stats.misses(), 2);
+/// assert_eq!(stats.len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct CachedParser {
+    options: ParseOptions,
+    lru: Mutex<Lru>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CachedParser {
+    /// Creates a parser that caches up to 1000 results, like the original
+    /// `index.js`.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Creates a parser that caches up to `capacity` results.
+    ///
+    /// A capacity of `0` disables caching.
+    pub fn with_capacity(capacity: usize) -> Self {
+        CachedParser {
+            options: ParseOptions::default(),
+            lru: Mutex::new(Lru::new(capacity)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns a parser that uses the given [ParseOptions].
+    ///
+    /// Each parser only caches results for its own options, so parsers with
+    /// different options never share results.
+    pub fn with_options(mut self, options: ParseOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The [ParseOptions] that are used by this parser.
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    /// Returns the cached result for `giturl`, or parses it with
+    /// [HostedGitInfo::from_url_with_options] and caches the result.
+    pub fn parse(&self, giturl: &str) -> Result<HostedGitInfo, ParseError> {
+        if let Some(result) = self.lock().get(giturl) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return result;
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        // the lock is not held while parsing, so that other threads are not
+        // blocked by it
+        let result = HostedGitInfo::from_url_with_options(giturl, &self.options);
+        self.lock().insert(giturl, result.clone());
+        result
+    }
+
+    /// Removes all cached results and resets the statistics.
+    pub fn clear(&self) {
+        self.lock().clear();
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    /// Returns the current cache statistics.
+    pub fn stats(&self) -> CacheStats {
+        let lru = self.lock();
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            len: lru.map.len(),
+            capacity: lru.capacity,
+        }
+    }
+
+    // a panic while holding the lock can not leave the cache in an
+    // inconsistent state, so poisoning is ignored
+    fn lock(&self) -> MutexGuard<'_, Lru> {
+        self.lru.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for CachedParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A snapshot of the statistics of a [CachedParser].
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct CacheStats {
+    hits: u64,
+    misses: u64,
+    len: usize,
+    capacity: usize,
+}
+
+impl CacheStats {
+    /// The number of calls that returned a cached result.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// The number of calls that had to parse the URL.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// The number of cached results.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no results are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The maximum number of cached results.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+type CachedResult = Result<HostedGitInfo, ParseError>;
+
+#[derive(Debug)]
+struct Node {
+    key: String,
+    value: CachedResult,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// A least-recently-used cache, implemented as a doubly linked list of
+/// nodes in a `Vec`, with the most recently used node at the head.
+#[derive(Debug)]
+struct Lru {
+    capacity: usize,
+    map: HashMap<String, usize>,
+    nodes: Vec<Node>,
+    head: Option<usize>,
+    tail: Option<usize>,
+}
+
+impl Lru {
+    fn new(capacity: usize) -> Self {
+        Lru {
+            capacity,
+            map: HashMap::new(),
+            nodes: Vec::new(),
+            head: None,
+            tail: None,
+        }
+    }
+
+    fn get(&mut self, key: &str) -> Option<CachedResult> {
+        let index = *self.map.get(key)?;
+        self.unlink(index);
+        self.push_front(index);
+        Some(self.nodes[index].value.clone())
+    }
+
+    fn insert(&mut self, key: &str, value: CachedResult) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if let Some(&index) = self.map.get(key) {
+            self.nodes[index].value = value;
+            self.unlink(index);
+            self.push_front(index);
+            return;
+        }
+
+        let index = if self.nodes.len() < self.capacity {
+            self.nodes.push(Node {
+                key: key.to_string(),
+                value,
+                prev: None,
+                next: None,
+            });
+            self.nodes.len() - 1
+        } else {
+            // reuse the node of the least recently used entry
+            let index = match self.tail {
+                Some(index) => index,
+                None => return,
+            };
+            self.unlink(index);
+            let node = &mut self.nodes[index];
+            self.map.remove(&node.key);
+            node.key = key.to_string();
+            node.value = value;
+            index
+        };
+
+        self.map.insert(key.to_string(), index);
+        self.push_front(index);
+    }
+
+    fn clear(&mut self) {
+        self.map.clear();
+        self.nodes.clear();
+        self.head = None;
+        self.tail = None;
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let (prev, next) = (self.nodes[index].prev, self.nodes[index].next);
+        match prev {
+            Some(prev) => self.nodes[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.nodes[next].prev = prev,
+            None => self.tail = prev,
+        }
+        self.nodes[index].prev = None;
+        self.nodes[index].next = None;
+    }
+
+    fn push_front(&mut self, index: usize) {
+        self.nodes[index].next = self.head;
+        if let Some(head) = self.head {
+            self.nodes[head].prev = Some(index);
+        }
+        self.head = Some(index);
+        if self.tail.is_none() {
+            self.tail = Some(index);
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use crate::remote::{common_dir, find_git_dir};
+use std::env;
+use std::fs;
+use std::io;
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+use std::str::Chars;
+use thiserror::Error;
+
+/// Errors that can occur while reading git repositories and config files.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// Failed to read a file from disk.
+    #[error("Failed to read file")]
+    Io(#[from] io::Error),
+
+    /// The config file does not follow the expected syntax.
+    #[error("Failed to parse config file on line {line}")]
+    Syntax { line: usize },
+
+    /// The path does not point to a work tree or bare repository.
+    #[error("Failed to find git repository at {}", .0.display())]
+    NotARepository(PathBuf),
+
+    /// Config files include each other too deeply, most likely in a cycle.
+    #[error("Failed to include {} because of too many nested includes", .0.display())]
+    IncludeDepth(PathBuf),
+}
+
+/// A single `key = value` line of a git config file.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub(crate) struct Entry {
+    /// lowercased section name, e.g. `remote`
+    pub section: String,
+    /// case-sensitive subsection name, e.g. `origin`
+    pub subsection: Option<String>,
+    /// lowercased key name, e.g. `url`
+    pub key: String,
+    /// `None` for keys without `=`, which git treats as boolean `true`
+    pub value: Option<String>,
+}
+
+/// A minimal reader for the [git-config](https://git-scm.com/docs/git-config#_syntax)
+/// file format.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub(crate) struct GitConfig {
+    entries: Vec<Entry>,
+}
+
+impl GitConfig {
+    /// Reads a config file and, like git itself, all files referenced by
+    /// `include.path` keys in it.
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        Self::from_file_with_depth(path, 0)
+    }
+
+    fn from_file_with_depth(path: &Path, depth: usize) -> Result<Self, ConfigError> {
+        // see `MAX_INCLUDE_DEPTH` in git's `config.c`
+        if depth > 10 {
+            return Err(ConfigError::IncludeDepth(path.to_path_buf()));
+        }
+
+        let config = Self::parse(&fs::read_to_string(path)?)?;
+
+        let mut entries = Vec::with_capacity(config.entries.len());
+        for entry in config.entries {
+            let include = match (&entry.subsection, &entry.value) {
+                (None, Some(value)) if entry.section == "include" && entry.key == "path" => {
+                    Some(resolve_include_path(path, value))
+                }
+                _ => None,
+            };
+
+            entries.push(entry);
+
+            if let Some(include) = include {
+                match Self::from_file_with_depth(&include, depth + 1) {
+                    Ok(included) => entries.extend(included.entries),
+                    // git silently ignores missing include files
+                    Err(ConfigError::Io(error)) if error.kind() == io::ErrorKind::NotFound => {}
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+
+        Ok(GitConfig { entries })
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ConfigError> {
+        let mut chars = input.chars().peekable();
+        let mut line = 1;
+        let mut section: Option<(String, Option<String>)> = None;
+        let mut entries = Vec::new();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\n' => line += 1,
+                c if c.is_whitespace() => {}
+                '#' | ';' => skip_line(&mut chars, &mut line),
+                '[' => section = Some(parse_section_header(&mut chars, line)?),
+                c if c.is_ascii_alphabetic() => {
+                    let (section, subsection) =
+                        section.clone().ok_or(ConfigError::Syntax { line })?;
+
+                    let mut key = c.to_ascii_lowercase().to_string();
+                    while let Some(&c) = chars.peek() {
+                        if !c.is_ascii_alphanumeric() && c != '-' {
+                            break;
+                        }
+                        key.push(c.to_ascii_lowercase());
+                        chars.next();
+                    }
+
+                    while matches!(chars.peek(), Some(' ') | Some('\t') | Some('\r')) {
+                        chars.next();
+                    }
+
+                    let value = match chars.peek() {
+                        Some('=') => {
+                            chars.next();
+                            Some(parse_value(&mut chars, &mut line)?)
+                        }
+                        None | Some('\n') | Some('#') | Some(';') => None,
+                        Some(_) => return Err(ConfigError::Syntax { line }),
+                    };
+
+                    entries.push(Entry {
+                        section,
+                        subsection,
+                        key,
+                        value,
+                    });
+                }
+                _ => return Err(ConfigError::Syntax { line }),
+            }
+        }
+
+        Ok(GitConfig { entries })
+    }
+
+    /// All entries in the order in which they appear in the file.
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter()
+    }
+}
+
+/// The system and global git config files that are read in addition to the
+/// config file of a repository, ordered from lowest to highest precedence.
+///
+/// ```no_run
+/// use hosted_git_info::{GitConfigFiles, UrlRewriter};
+///
+/// let mut files = GitConfigFiles::new();
+/// files.add("/etc/gitconfig").add("/home/me/.gitconfig");
+///
+/// let rewriter = UrlRewriter::from_config_files(&files, None::<&str>).unwrap();
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct GitConfigFiles {
+    paths: Vec<PathBuf>,
+}
+
+impl GitConfigFiles {
+    /// Creates an empty list, which skips the system and global config files.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the files that git itself would read.
+    ///
+    /// The `GIT_CONFIG_NOSYSTEM`, `GIT_CONFIG_SYSTEM`, `GIT_CONFIG_GLOBAL`,
+    /// `XDG_CONFIG_HOME` and `HOME` environment variables are respected.
+    pub fn from_env() -> Self {
+        let mut files = Self::new();
+
+        let no_system = env::var("GIT_CONFIG_NOSYSTEM")
+            .map(|value| is_truthy(&value))
+            .unwrap_or(false);
+        if !no_system {
+            files.add(
+                env::var_os("GIT_CONFIG_SYSTEM")
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| PathBuf::from("/etc/gitconfig")),
+            );
+        }
+
+        if let Some(global) = env::var_os("GIT_CONFIG_GLOBAL") {
+            files.add(global);
+        } else {
+            let home = env::var_os("HOME").map(PathBuf::from);
+            let xdg_config_home = env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| home.as_ref().map(|home| home.join(".config")));
+            if let Some(xdg_config_home) = xdg_config_home {
+                files.add(xdg_config_home.join("git").join("config"));
+            }
+            if let Some(home) = home {
+                files.add(home.join(".gitconfig"));
+            }
+        }
+
+        files
+    }
+
+    /// Appends a file with a higher precedence than the previous ones.
+    pub fn add<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    /// The files in the order in which they are read.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.paths.iter().map(PathBuf::as_path)
+    }
+
+    /// These files, followed by the config file of the repository at
+    /// `repository`, if given.
+    pub(crate) fn with_repository(
+        &self,
+        repository: Option<&Path>,
+    ) -> Result<Vec<PathBuf>, ConfigError> {
+        let mut files = self.paths.clone();
+
+        if let Some(repository) = repository {
+            let git_dir = find_git_dir(repository)?;
+            files.push(common_dir(&git_dir)?.join("config"));
+        }
+
+        Ok(files)
+    }
+}
+
+fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
+
+// relative include paths are resolved against the directory of the including file
+fn resolve_include_path(config_path: &Path, include: &str) -> PathBuf {
+    if let Some(rest) = include.strip_prefix("~/") {
+        if let Some(home) = env::var_os("HOME") {
+            return PathBuf::from(home).join(rest);
+        }
+    }
+
+    match config_path.parent() {
+        Some(parent) => parent.join(include),
+        None => PathBuf::from(include),
+    }
+}
+
+fn skip_line(chars: &mut Peekable<Chars<'_>>, line: &mut usize) {
+    for c in chars {
+        if c == '\n' {
+            *line += 1;
+            return;
+        }
+    }
+}
+
+// parses `[section]`, `[section "subsection"]` and the deprecated `[section.subsection]`
+fn parse_section_header(
+    chars: &mut Peekable<Chars<'_>>,
+    line: usize,
+) -> Result<(String, Option<String>), ConfigError> {
+    let mut name = String::new();
+    let mut subsection = None;
+
+    loop {
+        match chars.next() {
+            Some(']') => break,
+            Some(c) if c.is_ascii_alphanumeric() || c == '-' || c == '.' => {
+                name.push(c.to_ascii_lowercase())
+            }
+            Some(' ') | Some('\t') if subsection.is_none() => {
+                while matches!(chars.peek(), Some(' ') | Some('\t')) {
+                    chars.next();
+                }
+                if chars.next() != Some('"') {
+                    return Err(ConfigError::Syntax { line });
+                }
+
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) if c != '\n' => value.push(c),
+                            _ => return Err(ConfigError::Syntax { line }),
+                        },
+                        Some(c) if c != '\n' => value.push(c),
+                        _ => return Err(ConfigError::Syntax { line }),
+                    }
+                }
+                if chars.peek() != Some(&']') {
+                    return Err(ConfigError::Syntax { line });
+                }
+                subsection = Some(value);
+            }
+            _ => return Err(ConfigError::Syntax { line }),
+        }
+    }
+
+    if name.is_empty() {
+        return Err(ConfigError::Syntax { line });
+    }
+
+    if subsection.is_none() {
+        if let Some(dot) = name.find('.') {
+            subsection = Some(name[dot + 1..].to_string());
+            name.truncate(dot);
+        }
+    }
+
+    Ok((name, subsection))
+}
+
+// parses everything after the `=` up to (and including) the end of the line
+fn parse_value(chars: &mut Peekable<Chars<'_>>, line: &mut usize) -> Result<String, ConfigError> {
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut comment = false;
+    let mut pending_whitespace = 0;
+
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            if quoted {
+                return Err(ConfigError::Syntax { line: *line });
+            }
+            *line += 1;
+            return Ok(value);
+        }
+        if comment {
+            continue;
+        }
+        if c.is_whitespace() && !quoted {
+            // leading and trailing whitespace is dropped, inner whitespace is kept
+            if !value.is_empty() {
+                pending_whitespace += 1;
+            }
+            continue;
+        }
+        if (c == '#' || c == ';') && !quoted {
+            comment = true;
+            continue;
+        }
+
+        for _ in 0..pending_whitespace {
+            value.push(' ');
+        }
+        pending_whitespace = 0;
+
+        match c {
+            '"' => quoted = !quoted,
+            '\\' => match chars.next() {
+                Some('\n') => *line += 1,
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                    *line += 1;
+                }
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some('b') => value.push('\u{8}'),
+                Some('\\') => value.push('\\'),
+                Some('"') => value.push('"'),
+                _ => return Err(ConfigError::Syntax { line: *line }),
+            },
+            c => value.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(ConfigError::Syntax { line: *line });
+    }
+
+    Ok(value)
+}
@@ -0,0 +1,343 @@
+//! C bindings for non-Rust consumers.
+//!
+//! The C header is generated by [cbindgen](https://github.com/eqrion/cbindgen)
+//! and can be found at `include/hosted_git_info.h`. Building with the `ffi`
+//! feature regenerates it in `$OUT_DIR`, which requires a recent Rust version.
+//!
+//! The shared library is not built by default, use
+//! `cargo rustc --lib --release --features ffi --crate-type cdylib` to build
+//! it.
+//!
+//! All strings that are returned by these functions are owned by the caller
+//! and must be released with [hgi_string_free].
+
+use crate::auth::zeroize_bytes;
+use crate::{HostedGitInfo, ParseError, Provider};
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+/// The status codes that are returned by the fallible functions.
+#[repr(C)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum HgiStatus {
+    Ok = 0,
+    /// A required pointer argument was `NULL`.
+    NullPointer = 1,
+    /// A string argument or result was not valid UTF-8, or contained a NUL byte.
+    InvalidString = 2,
+    /// See [ParseError::InvalidUrl].
+    InvalidUrl = 3,
+    /// See [ParseError::InvalidUriEncoding].
+    InvalidUriEncoding = 4,
+    /// See [ParseError::UnknownUrl].
+    UnknownUrl = 5,
+    /// The provider does not support the requested format.
+    UnsupportedFormat = 6,
+    /// See [ParseError::RequiresLeniency].
+    RequiresLeniency = 7,
+    /// The format is not one of the [HgiFormat] values.
+    UnknownFormat = 8,
+}
+
+impl From<ParseError> for HgiStatus {
+    fn from(error: ParseError) -> Self {
+        match error {
+            ParseError::InvalidUrl(_) => HgiStatus::InvalidUrl,
+            ParseError::InvalidUriEncoding(_) => HgiStatus::InvalidUriEncoding,
+            ParseError::UnknownUrl => HgiStatus::UnknownUrl,
+            ParseError::RequiresLeniency(_) => HgiStatus::RequiresLeniency,
+        }
+    }
+}
+
+/// See [Provider].
+#[repr(C)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum HgiProvider {
+    Bitbucket = 0,
+    Gist = 1,
+    Github = 2,
+    Gitlab = 3,
+}
+
+impl From<Provider> for HgiProvider {
+    fn from(provider: Provider) -> Self {
+        match provider {
+            Provider::BitBucket => HgiProvider::Bitbucket,
+            Provider::Gist => HgiProvider::Gist,
+            Provider::GitHub => HgiProvider::Github,
+            Provider::GitLab => HgiProvider::Gitlab,
+        }
+    }
+}
+
+/// The representations that can be rendered with [hgi_render].
+#[repr(C)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum HgiFormat {
+    /// See [HostedGitInfo::ssh].
+    Ssh = 0,
+    /// See [HostedGitInfo::sshurl].
+    SshUrl = 1,
+    /// See [HostedGitInfo::https].
+    Https = 2,
+    /// See [HostedGitInfo::git].
+    Git = 3,
+    /// See [HostedGitInfo::shortcut].
+    Shortcut = 4,
+    /// See [HostedGitInfo::path].
+    Path = 5,
+    /// See [HostedGitInfo::browse] and [HostedGitInfo::browse_file].
+    Browse = 6,
+    /// See [HostedGitInfo::docs].
+    Docs = 7,
+    /// See [HostedGitInfo::bugs].
+    Bugs = 8,
+    /// See [HostedGitInfo::tarball].
+    Tarball = 9,
+    /// See [HostedGitInfo::file].
+    File = 10,
+    /// The default representation, without credentials.
+    Default = 11,
+}
+
+impl TryFrom<c_int> for HgiFormat {
+    type Error = HgiStatus;
+
+    fn try_from(value: c_int) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => HgiFormat::Ssh,
+            1 => HgiFormat::SshUrl,
+            2 => HgiFormat::Https,
+            3 => HgiFormat::Git,
+            4 => HgiFormat::Shortcut,
+            5 => HgiFormat::Path,
+            6 => HgiFormat::Browse,
+            7 => HgiFormat::Docs,
+            8 => HgiFormat::Bugs,
+            9 => HgiFormat::Tarball,
+            10 => HgiFormat::File,
+            11 => HgiFormat::Default,
+            _ => return Err(HgiStatus::UnknownFormat),
+        })
+    }
+}
+
+/// An opaque handle to a parsed [HostedGitInfo].
+pub struct HgiInfo(HostedGitInfo);
+
+/// Parses a NUL-terminated URL and stores a new handle in `out`.
+///
+/// The handle must be released with [hgi_info_free].
+///
+/// # Safety
+///
+/// `url` must be `NULL` or a valid NUL-terminated string, and `out` must be
+/// `NULL` or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn hgi_parse(url: *const c_char, out: *mut *mut HgiInfo) -> HgiStatus {
+    if url.is_null() || out.is_null() {
+        return HgiStatus::NullPointer;
+    }
+
+    let url = match CStr::from_ptr(url).to_str() {
+        Ok(url) => url,
+        Err(_) => return HgiStatus::InvalidString,
+    };
+
+    match HostedGitInfo::from_url(url) {
+        Ok(info) => {
+            *out = Box::into_raw(Box::new(HgiInfo(info)));
+            HgiStatus::Ok
+        }
+        Err(error) => error.into(),
+    }
+}
+
+/// Releases a handle that was created by [hgi_parse].
+///
+/// # Safety
+///
+/// `info` must be `NULL` or a handle returned by [hgi_parse] that has not
+/// been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn hgi_info_free(info: *mut HgiInfo) {
+    if !info.is_null() {
+        drop(Box::from_raw(info));
+    }
+}
+
+/// Stores the provider of the URL in `out`.
+///
+/// # Safety
+///
+/// `info` must be `NULL` or a valid handle returned by [hgi_parse], and `out`
+/// must be `NULL` or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn hgi_provider(info: *const HgiInfo, out: *mut HgiProvider) -> HgiStatus {
+    if info.is_null() || out.is_null() {
+        return HgiStatus::NullPointer;
+    }
+
+    *out = (*info).0.provider().into();
+    HgiStatus::Ok
+}
+
+/// Returns the user or organization name, or `NULL` if there is none.
+///
+/// # Safety
+///
+/// `info` must be `NULL` or a valid handle returned by [hgi_parse].
+#[no_mangle]
+pub unsafe extern "C" fn hgi_user(info: *const HgiInfo) -> *mut c_char {
+    match info.as_ref() {
+        Some(info) => into_c_string(info.0.user()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Returns the project name.
+///
+/// # Safety
+///
+/// `info` must be `NULL` or a valid handle returned by [hgi_parse].
+#[no_mangle]
+pub unsafe extern "C" fn hgi_project(info: *const HgiInfo) -> *mut c_char {
+    match info.as_ref() {
+        Some(info) => into_c_string(Some(info.0.project())),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Returns the branch, tag or commit, or `NULL` if there is none.
+///
+/// # Safety
+///
+/// `info` must be `NULL` or a valid handle returned by [hgi_parse].
+#[no_mangle]
+pub unsafe extern "C" fn hgi_committish(info: *const HgiInfo) -> *mut c_char {
+    match info.as_ref() {
+        Some(info) => into_c_string(info.0.committish()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Returns the subdirectory of the repository, or `NULL` if there is none.
+///
+/// # Safety
+///
+/// `info` must be `NULL` or a valid handle returned by [hgi_parse].
+#[no_mangle]
+pub unsafe extern "C" fn hgi_directory(info: *const HgiInfo) -> *mut c_char {
+    match info.as_ref() {
+        Some(info) => into_c_string(info.0.directory()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Returns the unredacted authentication part, or `NULL` if there is none.
+///
+/// # Safety
+///
+/// `info` must be `NULL` or a valid handle returned by [hgi_parse].
+#[no_mangle]
+pub unsafe extern "C" fn hgi_auth(info: *const HgiInfo) -> *mut c_char {
+    match info.as_ref() {
+        Some(info) => into_c_string(info.0.auth()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Renders the URL in the given format and stores the new string in `out`.
+///
+/// `format` is one of the [HgiFormat] values. It is passed as an `int`,
+/// because values outside of the enum would be undefined behavior, and
+/// result in [HgiStatus::UnknownFormat] instead.
+///
+/// `path` is the file path for [HgiFormat::Browse] and [HgiFormat::File],
+/// and may be `NULL`.
+///
+/// # Safety
+///
+/// `info` must be a valid handle returned by [hgi_parse], `path` must be
+/// `NULL` or a valid NUL-terminated string, and `out` must be `NULL` or valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn hgi_render(
+    info: *const HgiInfo,
+    format: c_int,
+    path: *const c_char,
+    out: *mut *mut c_char,
+) -> HgiStatus {
+    if info.is_null() || out.is_null() {
+        return HgiStatus::NullPointer;
+    }
+
+    let format = match HgiFormat::try_from(format) {
+        Ok(format) => format,
+        Err(status) => return status,
+    };
+
+    let path = if path.is_null() {
+        None
+    } else {
+        match CStr::from_ptr(path).to_str() {
+            Ok(path) => Some(path),
+            Err(_) => return HgiStatus::InvalidString,
+        }
+    };
+
+    let info = &(*info).0;
+    let rendered = match format {
+        HgiFormat::Ssh => info.ssh(),
+        HgiFormat::SshUrl => info.sshurl(),
+        HgiFormat::Https => info.https(),
+        HgiFormat::Git => match info.git() {
+            Some(url) => url,
+            None => return HgiStatus::UnsupportedFormat,
+        },
+        HgiFormat::Shortcut => info.shortcut(),
+        HgiFormat::Path => info.path(),
+        HgiFormat::Browse => match path {
+            Some(path) => info.browse_file(path, None),
+            None => info.browse(),
+        },
+        HgiFormat::Docs => info.docs(),
+        HgiFormat::Bugs => info.bugs(),
+        HgiFormat::Tarball => info.tarball(),
+        HgiFormat::File => info.file(path.unwrap_or("")),
+        HgiFormat::Default => info.to_string(),
+    };
+
+    match CString::new(rendered) {
+        Ok(rendered) => {
+            *out = rendered.into_raw();
+            HgiStatus::Ok
+        }
+        Err(_) => HgiStatus::InvalidString,
+    }
+}
+
+/// Releases a string that was returned by one of the other functions, and
+/// overwrites it with zeros, because it may contain credentials.
+///
+/// # Safety
+///
+/// `string` must be `NULL` or a string returned by this library that has
+/// not been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn hgi_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        zeroize_bytes(&mut CString::from_raw(string).into_bytes());
+    }
+}
+
+// percent-decoded fields may contain NUL bytes, which can not be represented
+// as C strings, so they are returned as `NULL` too
+fn into_c_string(value: Option<&str>) -> *mut c_char {
+    value
+        .and_then(|value| CString::new(value).ok())
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
@@ -0,0 +1,163 @@
+use crate::origin::{Origin, OriginDebug};
+use crate::{Auth, DefaultRepresentation, HostedGitInfo, ParseError, ParseOptions, Provider};
+use std::borrow::Cow;
+use std::fmt;
+
+/// A borrowed version of [HostedGitInfo], which avoids allocations for the
+/// fields that appear verbatim in the input string.
+///
+/// Fields that had to be percent-decoded are allocated as needed.
+///
+/// ```
+/// use hosted_git_info::{HostedGitInfoRef, Provider};
+///
+/// let url = String::from("https://github.com/foo/bar.git#main");
+/// let info = HostedGitInfoRef::parse(&url).unwrap();
+/// assert_eq!(info.provider(), Provider::GitHub);
+/// assert_eq!(info.project(), "bar");
+///
+/// let owned = info.into_owned();
+/// assert_eq!(owned.committish(), Some("main"));
+/// ```
+#[derive(Eq, PartialEq, Clone)]
+pub struct HostedGitInfoRef<'a> {
+    pub(crate) provider: Provider,
+    pub(crate) user: Option<Cow<'a, str>>,
+    pub(crate) auth: Option<Cow<'a, str>>,
+    pub(crate) project: Cow<'a, str>,
+    pub(crate) committish: Option<Cow<'a, str>>,
+    pub(crate) directory: Option<Cow<'a, str>>,
+    pub(crate) filename: Option<Cow<'a, str>>,
+    pub(crate) default_representation: DefaultRepresentation,
+    pub(crate) input: &'a str,
+    pub(crate) scheme: Cow<'a, str>,
+    pub(crate) host: Option<Cow<'a, str>>,
+    pub(crate) port: Option<u16>,
+}
+
+impl<'a> HostedGitInfoRef<'a> {
+    /// Parses a URL string like [HostedGitInfo::from_url], but borrows the
+    /// fields from the input where possible.
+    pub fn parse(giturl: &'a str) -> Result<Self, ParseError> {
+        Self::parse_with_options(giturl, &ParseOptions::default())
+    }
+
+    /// Parses a URL string like [HostedGitInfo::from_url_with_options], but
+    /// borrows the fields from the input where possible.
+    pub fn parse_with_options(giturl: &'a str, options: &ParseOptions) -> Result<Self, ParseError> {
+        crate::parse(giturl, options)
+    }
+
+    /// See [HostedGitInfo::provider].
+    pub fn provider(&self) -> Provider {
+        self.provider
+    }
+
+    /// See [HostedGitInfo::user].
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    /// See [HostedGitInfo::auth].
+    pub fn auth(&self) -> Option<&str> {
+        self.auth.as_deref()
+    }
+
+    /// See [HostedGitInfo::project].
+    pub fn project(&self) -> &str {
+        &self.project
+    }
+
+    /// See [HostedGitInfo::committish].
+    pub fn committish(&self) -> Option<&str> {
+        self.committish.as_deref()
+    }
+
+    /// See [HostedGitInfo::directory].
+    pub fn directory(&self) -> Option<&str> {
+        self.directory.as_deref()
+    }
+
+    /// See [HostedGitInfo::filename].
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+
+    /// See [HostedGitInfo::default_representation].
+    pub fn default_representation(&self) -> DefaultRepresentation {
+        self.default_representation
+    }
+
+    /// See [HostedGitInfo::original_input].
+    pub fn original_input(&self) -> &'a str {
+        self.input
+    }
+
+    /// See [HostedGitInfo::scheme].
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// See [HostedGitInfo::host].
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    /// See [HostedGitInfo::port].
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    /// Converts this into an owned [HostedGitInfo].
+    ///
+    /// Fields that were already allocated, e.g. because they had to be
+    /// percent-decoded, are moved instead of copied.
+    pub fn into_owned(self) -> HostedGitInfo {
+        HostedGitInfo {
+            provider: self.provider,
+            user: self.user.map(Cow::into_owned),
+            auth: self.auth.map(|auth| Auth::from(auth.into_owned())),
+            project: self.project.into_owned(),
+            committish: self.committish.map(Cow::into_owned),
+            directory: self.directory.map(Cow::into_owned),
+            filename: self.filename.map(Cow::into_owned),
+            default_representation: self.default_representation,
+            origin: Some(Origin {
+                input: self.input.to_string(),
+                scheme: self.scheme.into_owned(),
+                host: self.host.map(Cow::into_owned),
+                port: self.port,
+            }),
+        }
+    }
+}
+
+impl<'a> From<HostedGitInfoRef<'a>> for HostedGitInfo {
+    fn from(info: HostedGitInfoRef<'a>) -> Self {
+        info.into_owned()
+    }
+}
+
+impl fmt::Debug for HostedGitInfoRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HostedGitInfoRef")
+            .field("provider", &self.provider)
+            .field("user", &self.user)
+            .field("auth", &self.auth().map(Auth::from))
+            .field("project", &self.project)
+            .field("committish", &self.committish)
+            .field("directory", &self.directory)
+            .field("filename", &self.filename)
+            .field("default_representation", &self.default_representation)
+            .field(
+                "origin",
+                &Some(OriginDebug {
+                    input: self.input,
+                    scheme: &self.scheme,
+                    host: self.host.as_deref(),
+                    port: self.port,
+                }),
+            )
+            .finish()
+    }
+}
@@ -0,0 +1,312 @@
+use crate::auth::looks_like_token;
+use crate::{DefaultRepresentation, HostedGitInfo, Provider, INSECURE_SCHEMES};
+use std::fmt;
+
+/// How serious a [Diagnostic] is.
+///
+/// Severities are ordered, so that CI jobs can fail on anything at or above
+/// a threshold, e.g. `severity >= Severity::Warning`.
+#[derive(Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Severity {
+    /// Worth knowing about, but usually intentional.
+    Info,
+    /// Probably a mistake, or a risk that should be accepted explicitly.
+    Warning,
+    /// Almost certainly a mistake or a security problem.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// The checks that are run by [HostedGitInfo::lint] and [audit].
+///
+/// The [code](Lint::code) and [severity](Lint::severity) of a lint never
+/// change, and new lints only get new codes.
+#[derive(Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Lint {
+    /// The URL uses a plaintext transport like `git://` or `http://`.
+    InsecureTransport,
+    /// The URL contains a username or password.
+    EmbeddedCredentials,
+    /// The URL contains a well-known access token, e.g. `ghp_…` or `glpat-…`.
+    EmbeddedToken,
+    /// The URL points to a branch, tag or the default branch instead of a
+    /// full commit hash, so the referenced code can change.
+    MutableRef,
+    /// The host contains non-ASCII or punycode (`xn--`) labels.
+    InternationalizedHost,
+    /// The host is not a supported domain, but looks like one, e.g.
+    /// `gıthub.com` or `gitlab.corn`.
+    LookalikeHost,
+}
+
+impl Lint {
+    /// All lints, in the order of their codes.
+    pub fn all() -> &'static [Lint] {
+        &[
+            Lint::InsecureTransport,
+            Lint::EmbeddedCredentials,
+            Lint::EmbeddedToken,
+            Lint::MutableRef,
+            Lint::InternationalizedHost,
+            Lint::LookalikeHost,
+        ]
+    }
+
+    /// The stable code of the lint, e.g. `HGI001`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Lint::InsecureTransport => "HGI001",
+            Lint::EmbeddedCredentials => "HGI002",
+            Lint::EmbeddedToken => "HGI003",
+            Lint::MutableRef => "HGI004",
+            Lint::InternationalizedHost => "HGI005",
+            Lint::LookalikeHost => "HGI006",
+        }
+    }
+
+    /// The severity of all diagnostics of this lint.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Lint::InsecureTransport => Severity::Warning,
+            Lint::EmbeddedCredentials => Severity::Warning,
+            Lint::EmbeddedToken => Severity::Error,
+            Lint::MutableRef => Severity::Info,
+            Lint::InternationalizedHost => Severity::Warning,
+            Lint::LookalikeHost => Severity::Error,
+        }
+    }
+
+    /// Returns the lint with the given code, e.g. `HGI001`.
+    pub fn from_code(code: &str) -> Option<Lint> {
+        Lint::all()
+            .iter()
+            .copied()
+            .find(|lint| lint.code().eq_ignore_ascii_case(code))
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// A single finding of [HostedGitInfo::lint] or [audit].
+///
+/// The message never contains secrets from the URL.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Diagnostic {
+    lint: Lint,
+    code: &'static str,
+    severity: Severity,
+    message: String,
+}
+
+impl Diagnostic {
+    fn new<S: Into<String>>(lint: Lint, message: S) -> Self {
+        Diagnostic {
+            lint,
+            code: lint.code(),
+            severity: lint.severity(),
+            message: message.into(),
+        }
+    }
+
+    /// The lint that produced this diagnostic.
+    pub fn lint(&self) -> Lint {
+        self.lint
+    }
+
+    /// The stable code of the lint, e.g. `HGI001`.
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// The severity of the lint.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// A human-readable description of the finding.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Formatted like `warning[HGI001]: URL uses the plaintext `git` transport`.
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)
+    }
+}
+
+/// Checks a URL for security problems, including look-alike hosts that
+/// [HostedGitInfo::from_url] does not recognize at all.
+///
+/// If the URL can be parsed, the diagnostics of [HostedGitInfo::lint] are
+/// included as well.
+///
+/// ```
+/// use hosted_git_info::{audit, Lint, Severity};
+///
+/// let diagnostics = audit("https://gıthub.com/foo/bar.git");
+/// assert_eq!(diagnostics[0].lint(), Lint::InternationalizedHost);
+/// assert_eq!(diagnostics[1].lint(), Lint::LookalikeHost);
+/// assert_eq!(diagnostics[1].severity(), Severity::Error);
+/// ```
+pub fn audit(giturl: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if let Some(host) = host(giturl) {
+        let host = host.to_lowercase();
+
+        if !host.is_ascii() || host.split('.').any(|label| label.starts_with("xn--")) {
+            diagnostics.push(Diagnostic::new(
+                Lint::InternationalizedHost,
+                format!("host `{}` is an internationalized domain name", host),
+            ));
+        }
+
+        if let Some(provider) = lookalike(&host) {
+            diagnostics.push(Diagnostic::new(
+                Lint::LookalikeHost,
+                format!("host `{}` looks like `{}`", host, provider.domain()),
+            ));
+        }
+    }
+
+    if let Ok(info) = HostedGitInfo::from_url(giturl) {
+        diagnostics.extend(lint(&info));
+    }
+
+    diagnostics
+}
+
+pub(crate) fn lint(info: &HostedGitInfo) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let insecure_scheme = match info.scheme() {
+        Some(scheme) => INSECURE_SCHEMES.iter().find(|s| **s == scheme).copied(),
+        None if info.default_representation() == DefaultRepresentation::Git => Some("git"),
+        None => None,
+    };
+    if let Some(scheme) = insecure_scheme {
+        diagnostics.push(Diagnostic::new(
+            Lint::InsecureTransport,
+            format!("URL uses the plaintext `{}` transport", scheme),
+        ));
+    }
+
+    if let Some(auth) = info.credentials() {
+        let token = auth.as_str().split(':').find(|part| looks_like_token(part));
+        if token.is_some() {
+            diagnostics.push(Diagnostic::new(
+                Lint::EmbeddedToken,
+                format!("URL contains an access token (`{}`)", auth),
+            ));
+        } else {
+            diagnostics.push(Diagnostic::new(
+                Lint::EmbeddedCredentials,
+                format!("URL contains credentials (`{}`)", auth),
+            ));
+        }
+    }
+
+    // an empty committish, e.g. of `github:foo/bar#`, checks out the default
+    // branch too
+    match info
+        .committish()
+        .filter(|committish| !committish.is_empty())
+    {
+        Some(committish) if is_commit_hash(committish) => {}
+        Some(committish) => diagnostics.push(Diagnostic::new(
+            Lint::MutableRef,
+            format!("`{}` is not a full commit hash", committish),
+        )),
+        None => diagnostics.push(Diagnostic::new(
+            Lint::MutableRef,
+            "URL points to the default branch",
+        )),
+    }
+
+    diagnostics
+}
+
+// full SHA-1 or SHA-256 object names
+fn is_commit_hash(committish: &str) -> bool {
+    (committish.len() == 40 || committish.len() == 64)
+        && committish.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+// the host of `scheme://[user@]host[:port]/…` or `[user@]host:path`, without
+// relying on `Url`, which would convert internationalized hosts to punycode
+fn host(giturl: &str) -> Option<&str> {
+    let start = giturl.find("://").map_or(0, |index| index + 3);
+    let rest = &giturl[start..];
+    let authority = &rest[..rest
+        .find(|c| c == '/' || c == '#' || c == '?')
+        .unwrap_or(rest.len())];
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    let host = host.split(':').next().unwrap_or(host);
+
+    // shorthands like `foo/bar` or `github:foo/bar` have no host
+    if host.contains('.') {
+        Some(host)
+    } else {
+        None
+    }
+}
+
+// the supported domain that the host resembles, without being equal to it
+pub(crate) fn lookalike(host: &str) -> Option<Provider> {
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    let skeleton = skeleton(host);
+
+    Provider::all()
+        .iter()
+        .copied()
+        .find(|provider| host != provider.domain() && skeleton == self::skeleton(provider.domain()))
+}
+
+// maps characters that are commonly confused with each other to a single
+// representative, similar to the "skeleton" of Unicode TR #39
+fn skeleton(host: &str) -> String {
+    let mapped: String = host
+        .chars()
+        .map(|c| match c {
+            'а' | 'α' => 'a',
+            'с' | 'ϲ' => 'c',
+            'ԁ' => 'd',
+            'е' | 'ε' => 'e',
+            'ɡ' => 'g',
+            'һ' => 'h',
+            'i' | 'ı' | 'і' | 'ι' | '1' | '|' | 'ӏ' | 'l' => 'l',
+            'ј' => 'j',
+            'о' | 'ο' | '0' => 'o',
+            'р' | 'ρ' => 'p',
+            'ѕ' => 's',
+            'т' => 't',
+            'υ' => 'u',
+            'ν' => 'v',
+            'х' => 'x',
+            'у' => 'y',
+            c => c,
+        })
+        .collect();
+
+    mapped.replace("rn", "m").replace("vv", "w")
+}
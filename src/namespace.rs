@@ -0,0 +1,161 @@
+use std::fmt;
+use thiserror::Error;
+
+// GitLab supports up to 20 levels of subgroups below the top-level group
+const MAX_DEPTH: usize = 21;
+const MAX_SEGMENT_LENGTH: usize = 255;
+
+/// The owner of a repository, split into its top-level group and
+/// subgroups.
+///
+/// Only GitLab supports nested subgroups, for all other providers the
+/// namespace is a single user or organization.
+///
+/// ```
+/// use hosted_git_info::HostedGitInfo;
+///
+/// let info = HostedGitInfo::from_url("https://gitlab.com/a/b/c/project").unwrap();
+/// let namespace = info.namespace().unwrap();
+/// assert_eq!(namespace.as_str(), "a/b/c");
+/// assert_eq!(namespace.group(), "a");
+/// assert_eq!(namespace.subgroups().collect::<Vec<_>>(), ["b", "c"]);
+/// assert_eq!(namespace.parent().unwrap().as_str(), "a/b");
+/// ```
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct Namespace<'a> {
+    path: &'a str,
+}
+
+impl<'a> Namespace<'a> {
+    /// Wraps a `/`-separated namespace path, e.g. `group/subgroup`.
+    pub fn new(path: &'a str) -> Self {
+        Namespace { path }
+    }
+
+    /// The complete namespace path, e.g. `group/subgroup`.
+    pub fn as_str(&self) -> &'a str {
+        self.path
+    }
+
+    /// The top-level group, user or organization.
+    pub fn group(&self) -> &'a str {
+        self.segments().next().unwrap_or(self.path)
+    }
+
+    /// The nested subgroups below the top-level [group](Namespace::group),
+    /// outermost first.
+    pub fn subgroups(&self) -> impl Iterator<Item = &'a str> {
+        self.segments().skip(1)
+    }
+
+    /// All segments of the namespace path, starting with the top-level group.
+    pub fn segments(&self) -> impl Iterator<Item = &'a str> {
+        self.path.split('/')
+    }
+
+    /// The number of segments, i.e. `1` for a namespace without subgroups.
+    pub fn depth(&self) -> usize {
+        self.segments().count()
+    }
+
+    /// Returns `true` if the namespace has at least one subgroup.
+    pub fn has_subgroups(&self) -> bool {
+        self.path.contains('/')
+    }
+
+    /// The namespace without its innermost subgroup, if it has subgroups.
+    pub fn parent(&self) -> Option<Namespace<'a>> {
+        self.path
+            .rfind('/')
+            .map(|slash| Namespace::new(&self.path[..slash]))
+    }
+
+    /// Checks the namespace against the GitLab naming rules for group paths.
+    ///
+    /// Each segment may only contain ASCII letters, digits, `_`, `-` and
+    /// `.`, must not start with `-` or `.`, must not end with `.`, `.git` or
+    /// `.atom`, and must not be longer than 255 characters. At most 20
+    /// levels of subgroups are allowed.
+    ///
+    /// ```
+    /// use hosted_git_info::Namespace;
+    ///
+    /// assert!(Namespace::new("group/sub-group").validate().is_ok());
+    /// assert!(Namespace::new("group/-sub").validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), NamespaceError> {
+        if self.depth() > MAX_DEPTH {
+            return Err(NamespaceError::TooDeep(self.depth()));
+        }
+
+        for segment in self.segments() {
+            validate_segment(segment)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_segment(segment: &str) -> Result<(), NamespaceError> {
+    if segment.is_empty() {
+        return Err(NamespaceError::EmptySegment);
+    }
+    if segment.len() > MAX_SEGMENT_LENGTH {
+        return Err(NamespaceError::TooLong(segment.to_string()));
+    }
+
+    let invalid = segment
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || *c == '_' || *c == '-' || *c == '.'));
+    if let Some(character) = invalid {
+        return Err(NamespaceError::InvalidCharacter {
+            segment: segment.to_string(),
+            character,
+        });
+    }
+
+    if segment.starts_with('-') || segment.starts_with('.') {
+        return Err(NamespaceError::InvalidStart(segment.to_string()));
+    }
+
+    let lowercase = segment.to_lowercase();
+    if lowercase.ends_with('.') || lowercase.ends_with(".git") || lowercase.ends_with(".atom") {
+        return Err(NamespaceError::InvalidEnd(segment.to_string()));
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for Namespace<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.path)
+    }
+}
+
+/// Errors that can occur while validating a [Namespace].
+#[derive(Debug, Eq, PartialEq, Clone, Error)]
+pub enum NamespaceError {
+    /// The namespace contains an empty segment, e.g. `group//subgroup`.
+    #[error("Failed to validate namespace with an empty group name")]
+    EmptySegment,
+
+    /// A segment is longer than 255 characters.
+    #[error("Failed to validate group `{0}` because it is too long")]
+    TooLong(String),
+
+    /// A segment contains a character that is not allowed in group paths.
+    #[error("Failed to validate group `{segment}` with invalid character `{character}`")]
+    InvalidCharacter { segment: String, character: char },
+
+    /// A segment starts with `-` or `.`.
+    #[error("Failed to validate group `{0}` because it starts with a special character")]
+    InvalidStart(String),
+
+    /// A segment ends with `.`, `.git` or `.atom`.
+    #[error("Failed to validate group `{0}` because of its ending")]
+    InvalidEnd(String),
+
+    /// The namespace has more than 20 levels of subgroups.
+    #[error("Failed to validate namespace with {0} levels")]
+    TooDeep(usize),
+}
@@ -0,0 +1,108 @@
+use crate::{HostedGitInfo, ParseError};
+use std::fmt;
+
+/// The heuristics that are used to repair input that is not a valid URL,
+/// for compatibility with npm.
+#[derive(Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Copy, Clone)]
+pub enum Leniency {
+    /// `foo/bar` is expanded to `github:foo/bar`.
+    GitHubShorthand,
+    /// scp-style input like `git@github.com:foo/bar` is corrected to
+    /// `git+ssh://git@github.com/foo/bar`, and missing slashes after unknown
+    /// schemes are inserted.
+    ScpSyntax,
+    /// The unencrypted `git://` and `http://` schemes are accepted.
+    InsecureScheme,
+}
+
+impl fmt::Display for Leniency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Leniency::GitHubShorthand => "GitHub shorthand expansion",
+            Leniency::ScpSyntax => "correction of scp-style syntax",
+            Leniency::InsecureScheme => "an insecure scheme",
+        })
+    }
+}
+
+/// Options to control how lenient the parser is.
+///
+/// The [default](ParseOptions::default) options accept everything that the
+/// original `hosted-git-info` package accepts. The [strict](ParseOptions::strict)
+/// options only accept unambiguous, secure URLs and shortcuts, and return
+/// [ParseError::RequiresLeniency] for input that would only be accepted by
+/// the lenient parser.
+///
+/// ```
+/// use hosted_git_info::{Leniency, ParseError, ParseOptions};
+///
+/// let strict = ParseOptions::strict();
+/// assert!(strict.parse("https://github.com/foo/bar.git").is_ok());
+/// assert!(strict.parse("github:foo/bar").is_ok());
+/// assert_eq!(
+///     strict.parse("foo/bar"),
+///     Err(ParseError::RequiresLeniency(Leniency::GitHubShorthand))
+/// );
+/// assert_eq!(
+///     strict.parse("git@github.com:foo/bar.git"),
+///     Err(ParseError::RequiresLeniency(Leniency::ScpSyntax))
+/// );
+/// ```
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct ParseOptions {
+    github_shorthand: bool,
+    scp_syntax: bool,
+    insecure_schemes: bool,
+}
+
+impl ParseOptions {
+    /// Options that accept everything that npm accepts. Same as [ParseOptions::default].
+    pub fn lenient() -> Self {
+        ParseOptions {
+            github_shorthand: true,
+            scp_syntax: true,
+            insecure_schemes: true,
+        }
+    }
+
+    /// Options that reject all [Leniency] heuristics.
+    pub fn strict() -> Self {
+        ParseOptions {
+            github_shorthand: false,
+            scp_syntax: false,
+            insecure_schemes: false,
+        }
+    }
+
+    /// Returns a copy that allows or rejects the given [Leniency].
+    pub fn allow(mut self, leniency: Leniency, allow: bool) -> Self {
+        match leniency {
+            Leniency::GitHubShorthand => self.github_shorthand = allow,
+            Leniency::ScpSyntax => self.scp_syntax = allow,
+            Leniency::InsecureScheme => self.insecure_schemes = allow,
+        }
+        self
+    }
+
+    /// Whether the given [Leniency] is allowed.
+    pub fn allows(&self, leniency: Leniency) -> bool {
+        match leniency {
+            Leniency::GitHubShorthand => self.github_shorthand,
+            Leniency::ScpSyntax => self.scp_syntax,
+            Leniency::InsecureScheme => self.insecure_schemes,
+        }
+    }
+
+    /// Parses a URL string with these options.
+    ///
+    /// See [HostedGitInfo::from_url_with_options].
+    pub fn parse(&self, giturl: &str) -> Result<HostedGitInfo, ParseError> {
+        HostedGitInfo::from_url_with_options(giturl, self)
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::lenient()
+    }
+}
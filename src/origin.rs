@@ -0,0 +1,79 @@
+use crate::auth::{redact_credentials, userinfo_range, zeroize};
+use std::fmt;
+
+/// How a [HostedGitInfo](crate::HostedGitInfo) was written in the input.
+///
+/// The input may contain credentials, so it is overwritten with zeros when
+/// it is dropped, like [Auth](crate::Auth).
+#[derive(Eq, PartialEq, Clone)]
+pub(crate) struct Origin {
+    pub(crate) input: String,
+    pub(crate) scheme: String,
+    pub(crate) host: Option<String>,
+    pub(crate) port: Option<u16>,
+}
+
+impl Origin {
+    /// The `<USER>[:<PASSWORD>]` part of the input, if there is one.
+    pub(crate) fn userinfo(&self) -> Option<&str> {
+        userinfo_range(&self.input).map(|range| &self.input[range])
+    }
+
+    /// Removes the `<USER>[:<PASSWORD>]@` part from the input.
+    pub(crate) fn without_userinfo(&self) -> Self {
+        let input = match userinfo_range(&self.input) {
+            Some(range) => format!(
+                "{}{}",
+                &self.input[..range.start],
+                &self.input[range.end + 1..]
+            ),
+            None => self.input.clone(),
+        };
+
+        Origin {
+            input,
+            scheme: self.scheme.clone(),
+            host: self.host.clone(),
+            port: self.port,
+        }
+    }
+}
+
+impl Drop for Origin {
+    fn drop(&mut self) {
+        zeroize(&mut self.input);
+    }
+}
+
+impl fmt::Debug for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        OriginDebug {
+            input: &self.input,
+            scheme: &self.scheme,
+            host: self.host.as_deref(),
+            port: self.port,
+        }
+        .fmt(f)
+    }
+}
+
+/// The [Debug](fmt::Debug) output of an [Origin], which can also be built
+/// from borrowed fields.
+pub(crate) struct OriginDebug<'a> {
+    pub(crate) input: &'a str,
+    pub(crate) scheme: &'a str,
+    pub(crate) host: Option<&'a str>,
+    pub(crate) port: Option<u16>,
+}
+
+impl fmt::Debug for OriginDebug<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the input may contain credentials, so they are redacted like `Auth`
+        f.debug_struct("Origin")
+            .field("input", &redact_credentials(self.input))
+            .field("scheme", &self.scheme)
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .finish()
+    }
+}
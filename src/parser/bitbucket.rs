@@ -1,6 +1,5 @@
-use super::{ParsedSegments, Parser};
+use super::{ParsedSegments, Parser, Template};
 use crate::{ParseError, Provider};
-use std::str;
 use url::Url;
 
 #[derive(Debug, Eq, PartialEq)]
@@ -11,17 +10,14 @@ impl Parser for BitbucketParser {
         Provider::BitBucket
     }
 
-    fn supports_scheme(&self, scheme: &str) -> bool {
-        matches!(scheme, "git+ssh" | "git+https" | "ssh" | "https")
-    }
-
     fn extract<'a>(&self, url: &'a Url) -> Result<ParsedSegments<'a>, ParseError> {
         // let [, user, project, aux] = url.pathname.split('/', 4)
-        let mut path_segments = url.path().split('/');
+        let mut path_segments = url.path().splitn(5, '/');
         let _ = path_segments.next();
         let user = path_segments.next();
         let project = path_segments.next();
         let aux = path_segments.next();
+        let rest = path_segments.next();
 
         // if (['get'].includes(aux)) {
         //   return
@@ -43,13 +39,38 @@ impl Parser for BitbucketParser {
         }
 
         // return { user, project, committish: url.hash.slice(1) }
-        let committish = url.fragment();
+        //
+        // `src/<committish>/<directory>` is the Bitbucket equivalent of the
+        // GitHub tree URLs, with the same ambiguity: the committish is the
+        // first segment, so branches with slashes are only recognized when
+        // the slashes are encoded as `%2F`
+        let (committish, directory) = match (aux, rest) {
+            (Some("src"), Some(rest)) if !rest.is_empty() => {
+                let mut rest = rest.splitn(2, '/');
+                let committish = rest.next();
+                let directory = rest.next().filter(|directory| !directory.is_empty());
+                (committish, directory)
+            }
+            _ => (url.fragment(), None),
+        };
 
         // return { user, project, committish }
         Ok(ParsedSegments {
             user,
             project,
             committish,
+            directory,
+            filename: None,
         })
     }
+
+    // tarballtemplate: ({ domain, user, project, committish }) => `https://${domain}/${user}/${project}/get/${maybeEncode(committish) || 'master'}.tar.gz`
+    fn tarball(&self, t: &Template<'_>) -> String {
+        format!(
+            "https://{}/{}/get/{}.tar.gz",
+            self.provider().domain(),
+            t.user_project(),
+            t.encoded_committish_or_master()
+        )
+    }
 }
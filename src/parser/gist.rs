@@ -1,6 +1,5 @@
-use super::{ParsedSegments, Parser};
+use super::{format_hash_fragment, maybe_join, ParsedSegments, Parser, Template};
 use crate::{ParseError, Provider};
-use std::str;
 use url::Url;
 
 #[derive(Debug, Eq, PartialEq)]
@@ -11,24 +10,43 @@ impl Parser for GistParser {
         Provider::Gist
     }
 
-    fn supports_scheme(&self, scheme: &str) -> bool {
-        matches!(scheme, "git" | "git+ssh" | "git+https" | "ssh" | "https")
-    }
-
     fn extract<'a>(&self, url: &'a Url) -> Result<ParsedSegments<'a>, ParseError> {
+        // raw file URLs: `[<user>/]<id>/raw/[<revision>/]<filename>`, where
+        // `raw` can only be the second or third segment
+        let path = url.path().get(1..).unwrap_or_default();
+        let segments: Vec<&str> = path.split('/').collect();
+        if let Some(raw) = (1..=2).find(|index| segments.get(*index) == Some(&"raw")) {
+            return extract_raw(&segments[..raw], &segments[raw + 1..]);
+        }
+
+        // only raw files are served from `gist.githubusercontent.com`
+        if url.domain() == Some(RAW_DOMAIN) {
+            return Err(ParseError::UnknownUrl);
+        }
+
         // let [, user, project, aux] = url.pathname.split('/', 4)
         let mut path_segments = url.path().split('/');
         let _ = path_segments.next();
         let mut user = path_segments.next();
         let mut project = path_segments.next();
-        let aux = path_segments.next();
+        let mut aux = path_segments.next();
 
-        // if (aux === 'raw') {
-        //   return
-        // }
-        if aux == Some("raw") {
-            return Err(ParseError::UnknownUrl);
-        }
+        // embed scripts: `<id>.js`, `<user>/<id>.js` or `<user>/<id>/<revision>.js`
+        let is_present = |segment: Option<&str>| segment.map_or(false, |s| !s.is_empty());
+        let last = if is_present(aux) {
+            &mut aux
+        } else if is_present(project) {
+            &mut project
+        } else {
+            &mut user
+        };
+        let embed = match last.and_then(|segment| segment.strip_suffix(".js")) {
+            Some(stripped) => {
+                *last = Some(stripped);
+                true
+            }
+            None => false,
+        };
 
         // if (!project) {
         if project.is_none() || matches!(project, Some(project) if project.is_empty()) {
@@ -48,11 +66,219 @@ impl Parser for GistParser {
         let project = project.map(|project| project.strip_suffix(".git").unwrap_or(project));
 
         // return { user, project, committish: url.hash.slice(1) }
-        let committish = url.fragment();
+        //
+        // revision URLs have the revision as third segment, and the
+        // `#file-<name>` anchors of the web UI are not a committish. They
+        // only contain a normalized version of the filename, see
+        // `file_anchor()`, so they are not parsed as the filename either.
+        let revision = aux.filter(|aux| is_revision(aux));
+        let committish = url.fragment().filter(|fragment| !is_file_anchor(fragment));
+        let filename = match embed {
+            true => query_value(url, "file"),
+            false => None,
+        };
+
         Ok(ParsedSegments {
             user,
             project,
-            committish,
+            committish: revision.or(committish),
+            directory: None,
+            filename,
         })
     }
+
+    fn ssh(&self, t: &Template<'_>) -> String {
+        format!(
+            "{}:{}.git{}",
+            t.ssh_login(self.provider().domain()),
+            t.project,
+            t.hash()
+        )
+    }
+
+    // sshurltemplate: ({ domain, project, committish }) => `git+ssh://git@${domain}/${project}.git${maybeJoin('#', committish)}`
+    fn sshurl(&self, t: &Template<'_>) -> String {
+        format!(
+            "git+ssh://{}{}/{}.git{}",
+            t.ssh_login(self.provider().domain()),
+            t.ssh_port(),
+            t.project,
+            t.hash()
+        )
+    }
+
+    // browsetemplate: ({ domain, project, committish }) => `https://${domain}/${project}${maybeJoin('/', maybeEncode(committish))}`
+    fn browse(&self, t: &Template<'_>) -> String {
+        format!(
+            "https://{}/{}{}{}",
+            self.provider().domain(),
+            t.project,
+            maybe_join("/", t.encoded_committish().as_deref()),
+            file_anchor(t.filename)
+        )
+    }
+
+    // browsefiletemplate: ({ domain, project, committish, path, hashformat }) => `https://${domain}/${project}${maybeJoin('/', maybeEncode(committish))}${maybeJoin('#', hashformat(path))}`
+    fn browse_file(&self, t: &Template<'_>) -> String {
+        // hashformat: function (fragment) {
+        //   return fragment && 'file-' + formatHashFragment(fragment)
+        // }
+        //
+        // the path replaces the anchor of the filename
+        let filename = Some(t.file_path).filter(|path| !path.is_empty());
+        self.browse(&Template {
+            filename: filename.or(t.filename),
+            ..t.clone()
+        })
+    }
+
+    // docstemplate: ({ domain, project, committish }) => `https://${domain}/${project}${maybeJoin('/', maybeEncode(committish))}`
+    fn docs(&self, t: &Template<'_>) -> String {
+        self.browse(t)
+    }
+
+    // httpstemplate: ({ domain, project, committish }) => `git+https://${domain}/${project}.git${maybeJoin('#', committish)}`
+    fn https(&self, t: &Template<'_>) -> String {
+        format!(
+            "git+https://{}/{}.git{}",
+            self.provider().domain(),
+            t.project,
+            t.hash()
+        )
+    }
+
+    // filetemplate: ({ user, project, committish, path }) => `https://gist.githubusercontent.com/${user}/${project}/raw${maybeJoin('/', maybeEncode(committish))}/${path}`
+    fn file(&self, t: &Template<'_>) -> String {
+        format!(
+            "https://gist.githubusercontent.com/{}/raw{}/{}",
+            t.user_project(),
+            maybe_join("/", t.encoded_committish().as_deref()),
+            t.file_path
+        )
+    }
+
+    // shortcuttemplate: ({ type, project, committish }) => `${type}:${project}${maybeJoin('#', committish)}`
+    fn shortcut(&self, t: &Template<'_>) -> String {
+        format!("{}:{}{}", self.provider().shortcut(), t.project, t.hash())
+    }
+
+    // pathtemplate: ({ project, committish }) => `${project}${maybeJoin('#', committish)}`
+    fn path(&self, t: &Template<'_>) -> String {
+        format!("{}{}", t.project, t.hash())
+    }
+
+    // bugstemplate: ({ domain, project }) => `https://${domain}/${project}`
+    fn bugs(&self, t: &Template<'_>) -> String {
+        format!("https://{}/{}", self.provider().domain(), t.project)
+    }
+
+    // gittemplate: ({ domain, project, committish }) => `git://${domain}/${project}.git${maybeJoin('#', committish)}`
+    fn git(&self, t: &Template<'_>) -> Option<String> {
+        Some(format!(
+            "git://{}/{}.git{}",
+            self.provider().domain(),
+            t.project,
+            t.hash()
+        ))
+    }
+
+    // the `<script>` URL of the embed code, which only shows the given file
+    fn embed(&self, t: &Template<'_>) -> Option<String> {
+        Some(format!(
+            "https://{}/{}{}.js{}",
+            self.provider().domain(),
+            t.user_project(),
+            maybe_join("/", t.encoded_committish().as_deref()),
+            maybe_join("?file=", t.encoded_filename().as_deref())
+        ))
+    }
+
+    fn raw_file(&self, t: &Template<'_>) -> Option<String> {
+        let filename = t.encoded_filename()?;
+        Some(self.file(&Template {
+            file_path: &filename,
+            ..t.clone()
+        }))
+    }
+
+    fn download_zip(&self, t: &Template<'_>) -> Option<String> {
+        Some(format!(
+            "https://{}/{}/archive/{}.zip",
+            self.provider().domain(),
+            t.user_project(),
+            t.encoded_committish_or_master()
+        ))
+    }
+
+    // tarballtemplate: ({ project, committish }) => `https://codeload.github.com/gist/${project}/tar.gz/${maybeEncode(committish) || 'master'}`
+    fn tarball(&self, t: &Template<'_>) -> String {
+        format!(
+            "https://codeload.github.com/gist/{}/tar.gz/{}",
+            t.project,
+            t.encoded_committish_or_master()
+        )
+    }
+}
+
+/// The domain of raw gist files, which is also recognized as input.
+pub const RAW_DOMAIN: &str = "gist.githubusercontent.com";
+
+fn extract_raw<'a>(
+    before: &[&'a str],
+    after: &[&'a str],
+) -> Result<ParsedSegments<'a>, ParseError> {
+    let (user, project) = match before {
+        [project] => (None, *project),
+        [user, project] => (Some(*user), *project),
+        _ => return Err(ParseError::UnknownUrl),
+    };
+
+    let (revision, filename) = match after {
+        [filename] => (None, *filename),
+        [revision, filename] => (Some(*revision), *filename),
+        _ => return Err(ParseError::UnknownUrl),
+    };
+
+    if project.is_empty() || filename.is_empty() {
+        return Err(ParseError::UnknownUrl);
+    }
+
+    Ok(ParsedSegments {
+        user: user.filter(|user| !user.is_empty()),
+        project: Some(project),
+        committish: revision.filter(|revision| !revision.is_empty()),
+        directory: None,
+        filename: Some(filename),
+    })
+}
+
+// gist revisions are commit hashes
+fn is_revision(segment: &str) -> bool {
+    segment.len() >= 7 && segment.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn query_value<'a>(url: &'a Url, key: &str) -> Option<&'a str> {
+    url.query()?.split('&').find_map(|pair| {
+        let mut pair = pair.splitn(2, '=');
+        match (pair.next(), pair.next()) {
+            (Some(k), Some(value)) if k == key && !value.is_empty() => Some(value),
+            _ => None,
+        }
+    })
+}
+
+/// Whether the fragment is a `#file-<name>` anchor of the web UI.
+///
+/// The anchor is a lowercased version of the filename with `-` instead of
+/// punctuation, so the original filename can not be restored from it.
+pub fn is_file_anchor(fragment: &str) -> bool {
+    fragment.len() > "file-".len() && fragment.starts_with("file-")
+}
+
+// the `#file-<name>` anchor that shows the file on the web UI
+fn file_anchor(filename: Option<&str>) -> String {
+    match filename {
+        Some(filename) => format!("#file-{}", format_hash_fragment(filename)),
+        None => String::new(),
+    }
 }
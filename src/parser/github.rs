@@ -1,6 +1,5 @@
-use super::{ParsedSegments, Parser};
+use super::{encode, ParsedSegments, Parser, Template};
 use crate::{ParseError, Provider};
-use std::str;
 use url::Url;
 
 #[derive(Debug, Eq, PartialEq)]
@@ -11,21 +10,18 @@ impl Parser for GitHubParser {
         Provider::GitHub
     }
 
-    fn supports_scheme(&self, scheme: &str) -> bool {
-        matches!(
-            scheme,
-            "git" | "http" | "git+ssh" | "git+https" | "ssh" | "https"
-        )
-    }
-
     fn extract<'a>(&self, url: &'a Url) -> Result<ParsedSegments<'a>, ParseError> {
+        if url.domain() == Some(CODELOAD_DOMAIN) {
+            return extract_codeload(url);
+        }
+
         // let [, user, project, type, committish] = url.pathname.split('/', 5)
         let mut path_segments = url.path().splitn(5, '/');
         let _ = path_segments.next();
         let user = path_segments.next();
         let project = path_segments.next();
         let type_ = path_segments.next();
-        let mut committish = path_segments.next();
+        let rest = path_segments.next();
 
         // if (type && type !== 'tree') {
         //   return
@@ -34,13 +30,17 @@ impl Parser for GitHubParser {
         // if (!type) {
         //   committish = url.hash.slice(1)
         // }
-        if let Some(type_) = type_ {
-            if type_ != "tree" {
-                return Err(ParseError::UnknownUrl);
-            }
-        } else {
-            committish = url.fragment();
-        }
+        //
+        // release, commit and archive URLs are accepted too, and point to the
+        // repository at that tag or commit
+        let (committish, directory) = match type_ {
+            None => (url.fragment(), None),
+            Some("tree") => split_tree(rest),
+            Some(type_) => match deep_link_committish(type_, rest) {
+                Some(committish) => (Some(committish), None),
+                None => return Err(ParseError::UnknownUrl),
+            },
+        };
 
         // if (project && project.endsWith('.git')) {
         //   project = project.slice(0, -4)
@@ -59,6 +59,159 @@ impl Parser for GitHubParser {
             user,
             project,
             committish,
+            directory,
+            filename: None,
         })
     }
+
+    // filetemplate: ({ auth, user, project, committish, path }) => `https://${maybeJoin(auth, '@')}raw.githubusercontent.com/${user}/${project}/${maybeEncode(committish) || 'master'}/${path}`
+    fn file(&self, t: &Template<'_>) -> String {
+        format!(
+            "https://{}raw.githubusercontent.com/{}/{}/{}",
+            t.auth_prefix(),
+            t.user_project(),
+            t.encoded_committish_or_master(),
+            t.file_path_in_directory()
+        )
+    }
+
+    // gittemplate: ({ auth, domain, user, project, committish }) => `git://${maybeJoin(auth, '@')}${domain}/${user}/${project}.git${maybeJoin('#', committish)}`
+    fn git(&self, t: &Template<'_>) -> Option<String> {
+        Some(format!(
+            "git://{}{}/{}.git{}",
+            t.auth_prefix(),
+            self.provider().domain(),
+            t.user_project(),
+            t.hash()
+        ))
+    }
+
+    // tarballtemplate: ({ domain, user, project, committish }) => `https://codeload.${domain}/${user}/${project}/tar.gz/${maybeEncode(committish) || 'master'}`
+    fn tarball(&self, t: &Template<'_>) -> String {
+        format!(
+            "https://codeload.{}/{}/tar.gz/{}",
+            self.provider().domain(),
+            t.user_project(),
+            t.encoded_committish_or_master()
+        )
+    }
+
+    fn release_page(&self, t: &Template<'_>) -> Option<String> {
+        Some(format!(
+            "https://{}/{}/releases/tag/{}",
+            self.provider().domain(),
+            t.user_project(),
+            t.encoded_committish()?
+        ))
+    }
+
+    fn release_asset(&self, t: &Template<'_>) -> Option<String> {
+        Some(format!(
+            "https://{}/{}/releases/download/{}/{}",
+            self.provider().domain(),
+            t.user_project(),
+            t.encoded_committish()?,
+            encode(t.file_path)
+        ))
+    }
+
+    fn commit_page(&self, t: &Template<'_>) -> Option<String> {
+        Some(format!(
+            "https://{}/{}/commit/{}",
+            self.provider().domain(),
+            t.user_project(),
+            t.encoded_committish()?
+        ))
+    }
+
+    fn compare(&self, t: &Template<'_>, base: &str, head: &str) -> Option<String> {
+        Some(format!(
+            "https://{}/{}/compare/{}...{}",
+            self.provider().domain(),
+            t.user_project(),
+            encode(base),
+            encode(head)
+        ))
+    }
+}
+
+/// The domain of the archive downloads, which is also recognized as input.
+pub const CODELOAD_DOMAIN: &str = "codeload.github.com";
+
+// `tree/<committish>/<directory>`, where the committish is always the first
+// segment. A branch with slashes like `feature/foo` can not be told apart
+// from a directory without asking GitHub, so `tree/feature/foo/pkg` is parsed
+// as the committish `feature` in the directory `foo/pkg`. Such branches are
+// only recognized when the slashes are encoded, like `tree/feature%2Ffoo/pkg`,
+// which is also how they are rendered.
+fn split_tree(rest: Option<&str>) -> (Option<&str>, Option<&str>) {
+    let mut split_rest = rest.map(|rest| rest.splitn(2, '/'));
+    let committish = split_rest.as_mut().and_then(|rest| rest.next());
+    let directory = split_rest
+        .as_mut()
+        .and_then(|rest| rest.next())
+        .filter(|directory| !directory.is_empty());
+
+    (committish, directory)
+}
+
+// `releases/tag/<tag>`, `releases/download/<tag>/<asset>`, `commit/<sha>`
+// and `archive/[refs/(tags|heads)/]<ref>.(tar.gz|zip)`
+fn deep_link_committish<'a>(type_: &str, rest: Option<&'a str>) -> Option<&'a str> {
+    let rest = rest?;
+    let committish = match type_ {
+        "releases" => match rest.strip_prefix("tag/") {
+            Some(tag) => tag,
+            None => {
+                let download = rest.strip_prefix("download/")?;
+                download.rsplitn(2, '/').nth(1)?
+            }
+        },
+        "commit" => rest.split('/').next()?,
+        "archive" => {
+            let archive = strip_ref_prefix(rest);
+            archive
+                .strip_suffix(".tar.gz")
+                .or_else(|| archive.strip_suffix(".zip"))?
+        }
+        _ => return None,
+    };
+
+    Some(committish).filter(|committish| !committish.is_empty())
+}
+
+// `codeload.github.com/<user>/<project>/(tar.gz|zip)/<ref>`
+fn extract_codeload(url: &Url) -> Result<ParsedSegments<'_>, ParseError> {
+    let mut path_segments = url.path().splitn(5, '/');
+    let _ = path_segments.next();
+    let user = path_segments.next().filter(|user| !user.is_empty());
+    let project = path_segments.next().filter(|project| !project.is_empty());
+    let format = path_segments.next();
+    let committish = path_segments
+        .next()
+        .map(strip_ref_prefix)
+        .filter(|committish| !committish.is_empty());
+
+    match format {
+        Some("tar.gz") | Some("zip") | Some("legacy.tar.gz") | Some("legacy.zip") => {}
+        _ => return Err(ParseError::UnknownUrl),
+    }
+
+    if user.is_none() || project.is_none() || committish.is_none() {
+        return Err(ParseError::UnknownUrl);
+    }
+
+    Ok(ParsedSegments {
+        user,
+        project,
+        committish,
+        directory: None,
+        filename: None,
+    })
+}
+
+fn strip_ref_prefix(name: &str) -> &str {
+    name.strip_prefix("refs/tags/")
+        .or_else(|| name.strip_prefix("refs/heads/"))
+        .unwrap_or(name)
 }
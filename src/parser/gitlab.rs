@@ -1,6 +1,5 @@
-use super::{ParsedSegments, Parser};
+use super::{encode, format_hash_fragment, maybe_join, ParsedSegments, Parser, Template};
 use crate::{ParseError, Provider};
-use std::str;
 use url::Url;
 
 #[derive(Debug, Eq, PartialEq)]
@@ -11,13 +10,26 @@ impl Parser for GitLabParser {
         Provider::GitLab
     }
 
-    fn supports_scheme(&self, scheme: &str) -> bool {
-        matches!(scheme, "git+ssh" | "git+https" | "ssh" | "https")
-    }
-
     fn extract<'a>(&self, url: &'a Url) -> Result<ParsedSegments<'a>, ParseError> {
         // const path = url.pathname.slice(1)
-        let path = &url.path()[1..];
+        let path = url.path().get(1..).unwrap_or_default();
+
+        // `/-/tree/<ref>` is the browse URL of a branch, tag or commit, and
+        // the only `/-/` route that is accepted. Like the GitHub tree URLs,
+        // the ref is the first segment after it, so branches with slashes
+        // are only recognized when the slashes are encoded as `%2F`
+        let (path, committish, directory) = match path.find("/-/tree/") {
+            Some(index) => {
+                let mut rest = path[index + 8..].splitn(2, '/');
+                let committish = rest.next().unwrap_or_default();
+                if committish.is_empty() {
+                    return Err(ParseError::UnknownUrl);
+                }
+                let directory = rest.next().filter(|directory| !directory.is_empty());
+                (&path[..index], Some(committish), directory)
+            }
+            None => (path, url.fragment(), None),
+        };
 
         // if (path.includes('/-/') || path.includes('/archive.tar.gz')) {
         //   return
@@ -45,11 +57,109 @@ impl Parser for GitLabParser {
         }
 
         // return { user, project, committish: url.hash.slice(1) }
-        let committish = url.fragment();
         Ok(ParsedSegments {
             user,
             project,
             committish,
+            directory,
+            filename: None,
         })
     }
+
+    fn browse(&self, t: &Template<'_>) -> String {
+        format!(
+            "https://{}/{}{}",
+            self.provider().domain(),
+            t.user_project(),
+            t.tree(&route(t, "tree"))
+        )
+    }
+
+    fn browse_file(&self, t: &Template<'_>) -> String {
+        format!(
+            "https://{}/{}/{}/{}/{}{}",
+            self.provider().domain(),
+            t.user_project(),
+            route(t, "tree"),
+            t.encoded_committish_or_master(),
+            t.file_path_in_directory(),
+            maybe_join("#", Some(&format_hash_fragment(t.fragment.unwrap_or(""))))
+        )
+    }
+
+    fn file(&self, t: &Template<'_>) -> String {
+        format!(
+            "https://{}/{}/{}/{}/{}",
+            self.provider().domain(),
+            t.user_project(),
+            route(t, "raw"),
+            t.encoded_committish_or_master(),
+            t.file_path_in_directory()
+        )
+    }
+
+    // tarballtemplate: ({ domain, user, project, committish }) => `https://${domain}/${user}/${project}/repository/archive.tar.gz?ref=${maybeEncode(committish) || 'master'}`
+    fn tarball(&self, t: &Template<'_>) -> String {
+        if has_subgroups(t) {
+            let committish = t.encoded_committish_or_master();
+            return format!(
+                "https://{}/{}/-/archive/{}/{}-{}.tar.gz",
+                self.provider().domain(),
+                t.user_project(),
+                committish,
+                t.project,
+                committish
+            );
+        }
+
+        format!(
+            "https://{}/{}/repository/archive.tar.gz?ref={}",
+            self.provider().domain(),
+            t.user_project(),
+            t.encoded_committish_or_master()
+        )
+    }
+
+    fn release_page(&self, t: &Template<'_>) -> Option<String> {
+        Some(format!(
+            "https://{}/{}/-/releases/{}",
+            self.provider().domain(),
+            t.user_project(),
+            t.encoded_committish()?
+        ))
+    }
+
+    fn commit_page(&self, t: &Template<'_>) -> Option<String> {
+        Some(format!(
+            "https://{}/{}/-/commit/{}",
+            self.provider().domain(),
+            t.user_project(),
+            t.encoded_committish()?
+        ))
+    }
+
+    fn compare(&self, t: &Template<'_>, base: &str, head: &str) -> Option<String> {
+        Some(format!(
+            "https://{}/{}/-/compare/{}...{}",
+            self.provider().domain(),
+            t.user_project(),
+            encode(base),
+            encode(head)
+        ))
+    }
+}
+
+fn has_subgroups(t: &Template<'_>) -> bool {
+    t.user.map_or(false, |user| user.contains('/'))
+}
+
+// the legacy routes without `/-/` are ambiguous for projects in subgroups,
+// e.g. `group/sub/tree/main` could also be the project `main` in the
+// `group/sub/tree` subgroup, so only those use the new `-/tree` routes
+fn route(t: &Template<'_>, name: &str) -> String {
+    if has_subgroups(t) {
+        format!("-/{}", name)
+    } else {
+        name.to_string()
+    }
 }
@@ -1,8 +1,10 @@
 use self::bitbucket::BitbucketParser;
+pub(crate) use self::gist::is_file_anchor;
 use self::gist::GistParser;
 use self::github::GitHubParser;
 use self::gitlab::GitLabParser;
 use crate::{ParseError, Provider};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use std::fmt::{Debug, Formatter};
 use std::str;
 use url::Url;
@@ -12,30 +14,356 @@ mod gist;
 mod github;
 mod gitlab;
 
-pub fn parser_from_shortcut(shortcut: &str) -> Option<Box<dyn Parser>> {
-    match shortcut {
-        "bitbucket" => Some(Box::new(BitbucketParser {})),
-        "gist" => Some(Box::new(GistParser {})),
-        "github" => Some(Box::new(GitHubParser {})),
-        "gitlab" => Some(Box::new(GitLabParser {})),
-        _ => None,
+// the parsers are stateless, so a single static instance of each is shared
+// instead of boxing a new one for every call
+static BITBUCKET: BitbucketParser = BitbucketParser {};
+static GIST: GistParser = GistParser {};
+static GITHUB: GitHubParser = GitHubParser {};
+static GITLAB: GitLabParser = GitLabParser {};
+
+pub fn parser_from_shortcut(shortcut: &str) -> Option<&'static dyn Parser> {
+    Provider::from_shortcut(shortcut).map(parser_from_provider)
+}
+
+pub fn parser_from_provider(provider: Provider) -> &'static dyn Parser {
+    match provider {
+        Provider::BitBucket => &BITBUCKET,
+        Provider::Gist => &GIST,
+        Provider::GitHub => &GITHUB,
+        Provider::GitLab => &GITLAB,
     }
 }
 
-pub fn parser_from_domain(domain: &str) -> Option<Box<dyn Parser>> {
+pub fn parser_from_domain(domain: &str) -> Option<&'static dyn Parser> {
     match domain {
-        "bitbucket.org" => Some(Box::new(BitbucketParser {})),
-        "gist.github.com" => Some(Box::new(GistParser {})),
-        "github.com" => Some(Box::new(GitHubParser {})),
-        "gitlab.com" => Some(Box::new(GitLabParser {})),
-        _ => None,
+        gist::RAW_DOMAIN => Some(&GIST),
+        github::CODELOAD_DOMAIN => Some(&GITHUB),
+        domain => Provider::from_domain(domain).map(parser_from_provider),
     }
 }
 
 pub trait Parser {
     fn provider(&self) -> Provider;
-    fn supports_scheme(&self, scheme: &str) -> bool;
     fn extract<'a>(&self, url: &'a Url) -> Result<ParsedSegments<'a>, ParseError>;
+
+    fn supports_scheme(&self, scheme: &str) -> bool {
+        self.provider().supported_schemes().contains(&scheme)
+    }
+
+    fn tree_path(&self) -> &'static str {
+        self.provider().tree_path()
+    }
+
+    // sshtemplate: ({ domain, user, project, committish }) => `git@${domain}:${user}/${project}.git${maybeJoin('#', committish)}`
+    fn ssh(&self, t: &Template<'_>) -> String {
+        format!(
+            "{}:{}.git{}",
+            t.ssh_login(self.provider().domain()),
+            t.user_project(),
+            t.hash()
+        )
+    }
+
+    // sshurltemplate: ({ domain, user, project, committish }) => `git+ssh://git@${domain}/${user}/${project}.git${maybeJoin('#', committish)}`
+    fn sshurl(&self, t: &Template<'_>) -> String {
+        format!(
+            "git+ssh://{}{}/{}.git{}",
+            t.ssh_login(self.provider().domain()),
+            t.ssh_port(),
+            t.user_project(),
+            t.hash()
+        )
+    }
+
+    // browsetemplate: ({ domain, user, project, committish, treepath }) => `https://${domain}/${user}/${project}${maybeJoin('/', treepath, '/', maybeEncode(committish))}`
+    fn browse(&self, t: &Template<'_>) -> String {
+        format!(
+            "https://{}/{}{}",
+            self.provider().domain(),
+            t.user_project(),
+            t.tree(self.tree_path())
+        )
+    }
+
+    // browsefiletemplate: ({ domain, user, project, committish, treepath, path, fragment, hashformat }) => `https://${domain}/${user}/${project}/${treepath}/${maybeEncode(committish || 'master')}/${path}${maybeJoin('#', hashformat(fragment || ''))}`
+    fn browse_file(&self, t: &Template<'_>) -> String {
+        format!(
+            "https://{}/{}/{}/{}/{}{}",
+            self.provider().domain(),
+            t.user_project(),
+            self.tree_path(),
+            t.encoded_committish_or_master(),
+            t.file_path_in_directory(),
+            maybe_join("#", Some(&format_hash_fragment(t.fragment.unwrap_or(""))))
+        )
+    }
+
+    // docstemplate: ({ domain, user, project, treepath, committish }) => `https://${domain}/${user}/${project}${maybeJoin('/', treepath, '/', maybeEncode(committish))}#readme`
+    fn docs(&self, t: &Template<'_>) -> String {
+        format!("{}#readme", self.browse(t))
+    }
+
+    // httpstemplate: ({ auth, domain, user, project, committish }) => `git+https://${maybeJoin(auth, '@')}${domain}/${user}/${project}.git${maybeJoin('#', committish)}`
+    fn https(&self, t: &Template<'_>) -> String {
+        format!(
+            "git+https://{}{}/{}.git{}",
+            t.auth_prefix(),
+            self.provider().domain(),
+            t.user_project(),
+            t.hash()
+        )
+    }
+
+    // only GitHub and Gist define a `gittemplate`
+    fn git(&self, _t: &Template<'_>) -> Option<String> {
+        None
+    }
+
+    // filetemplate: ({ domain, user, project, committish, path }) => `https://${domain}/${user}/${project}/raw/${maybeEncode(committish) || 'master'}/${path}`
+    fn file(&self, t: &Template<'_>) -> String {
+        format!(
+            "https://{}/{}/raw/{}/{}",
+            self.provider().domain(),
+            t.user_project(),
+            t.encoded_committish_or_master(),
+            t.file_path_in_directory()
+        )
+    }
+
+    // shortcuttemplate: ({ type, user, project, committish }) => `${type}:${user}/${project}${maybeJoin('#', committish)}`
+    fn shortcut(&self, t: &Template<'_>) -> String {
+        format!(
+            "{}:{}{}",
+            self.provider().shortcut(),
+            t.user_project(),
+            t.hash()
+        )
+    }
+
+    // pathtemplate: ({ user, project, committish }) => `${user}/${project}${maybeJoin('#', committish)}`
+    fn path(&self, t: &Template<'_>) -> String {
+        format!("{}{}", t.user_project(), t.hash())
+    }
+
+    // bugstemplate: ({ domain, user, project }) => `https://${domain}/${user}/${project}/issues`
+    fn bugs(&self, t: &Template<'_>) -> String {
+        format!(
+            "https://{}/{}/issues",
+            self.provider().domain(),
+            t.user_project()
+        )
+    }
+
+    // only gists can be embedded, have single raw files and zip downloads
+    fn embed(&self, _t: &Template<'_>) -> Option<String> {
+        None
+    }
+
+    fn raw_file(&self, _t: &Template<'_>) -> Option<String> {
+        None
+    }
+
+    fn download_zip(&self, _t: &Template<'_>) -> Option<String> {
+        None
+    }
+
+    // only GitHub and GitLab have release, commit and compare pages, which
+    // all need a committish except for `compare`
+    fn release_page(&self, _t: &Template<'_>) -> Option<String> {
+        None
+    }
+
+    // the asset name is passed as the `file_path`
+    fn release_asset(&self, _t: &Template<'_>) -> Option<String> {
+        None
+    }
+
+    fn commit_page(&self, _t: &Template<'_>) -> Option<String> {
+        None
+    }
+
+    fn compare(&self, _t: &Template<'_>, _base: &str, _head: &str) -> Option<String> {
+        None
+    }
+
+    // every host defines its own `tarballtemplate`
+    fn tarball(&self, t: &Template<'_>) -> String;
+}
+
+// encodeURIComponent() escapes everything except: A-Z a-z 0-9 - _ . ! ~ * ' ( )
+const URI_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'!')
+    .remove(b'~')
+    .remove(b'*')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')');
+
+/// The values that are filled into the URL templates.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct Template<'a> {
+    pub auth: Option<&'a str>,
+    pub user: Option<&'a str>,
+    pub project: &'a str,
+    pub committish: Option<&'a str>,
+    /// the subdirectory of a monorepo, without leading or trailing slashes
+    pub directory: Option<&'a str>,
+    /// the name of a single gist file
+    pub filename: Option<&'a str>,
+    /// the file path, without a leading slash
+    pub file_path: &'a str,
+    pub fragment: Option<&'a str>,
+    /// the login user of the SSH URLs instead of `git`
+    pub ssh_user: Option<&'a str>,
+    /// the host of the SSH URLs instead of the domain, e.g. an SSH alias
+    pub ssh_host: Option<&'a str>,
+    /// the port of the `git+ssh://` URLs
+    pub ssh_port: Option<u16>,
+}
+
+impl<'a> Template<'a> {
+    /// `git@${domain}`, or the SSH user and host if they are set
+    pub fn ssh_login(&self, domain: &str) -> String {
+        format!(
+            "{}@{}",
+            self.ssh_user.unwrap_or("git"),
+            self.ssh_host.unwrap_or(domain)
+        )
+    }
+
+    /// `${maybeJoin(':', port)}` for the `git+ssh://` URLs
+    pub fn ssh_port(&self) -> String {
+        self.ssh_port
+            .map(|port| format!(":{}", port))
+            .unwrap_or_default()
+    }
+
+    /// `${user}/${project}`, or just `${project}` if there is no user
+    pub fn user_project(&self) -> String {
+        match self.user {
+            Some(user) => format!("{}/{}", user, self.project),
+            None => self.project.to_string(),
+        }
+    }
+
+    /// `${maybeJoin('#', committish)}`, followed by the npm-style
+    /// `::path:<directory>` attribute if there is a directory
+    pub fn hash(&self) -> String {
+        match (self.committish, self.directory) {
+            (Some(committish), Some(directory)) if !committish.is_empty() => {
+                format!("#{}::path:{}", committish, directory)
+            }
+            (_, Some(directory)) => format!("#path:{}", directory),
+            (committish, None) => maybe_join("#", committish),
+        }
+    }
+
+    /// `${maybeJoin('/', treepath, '/', maybeEncode(committish))}`, followed
+    /// by the directory, which requires a committish and falls back to
+    /// `master`
+    pub fn tree(&self, tree_path: &str) -> String {
+        match self.encoded_directory() {
+            Some(directory) => format!(
+                "/{}/{}/{}",
+                tree_path,
+                self.encoded_committish_or_master(),
+                directory
+            ),
+            None => maybe_join(
+                &format!("/{}/", tree_path),
+                self.encoded_committish().as_deref(),
+            ),
+        }
+    }
+
+    /// The file path relative to the repository root, i.e. prefixed with the
+    /// directory
+    pub fn file_path_in_directory(&self) -> String {
+        match self.directory {
+            Some(directory) => format!("{}/{}", directory, self.file_path),
+            None => self.file_path.to_string(),
+        }
+    }
+
+    /// `${maybeEncode(filename)}`
+    pub fn encoded_filename(&self) -> Option<String> {
+        self.filename
+            .map(|filename| utf8_percent_encode(filename, URI_COMPONENT).to_string())
+    }
+
+    /// The directory with each segment encoded like `maybeEncode()`
+    fn encoded_directory(&self) -> Option<String> {
+        self.directory.map(|directory| {
+            directory
+                .split('/')
+                .map(|segment| utf8_percent_encode(segment, URI_COMPONENT).to_string())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+    }
+
+    /// `${maybeJoin(auth, '@')}`
+    pub fn auth_prefix(&self) -> String {
+        match self.auth {
+            Some(auth) if !auth.is_empty() => format!("{}@", auth),
+            _ => String::new(),
+        }
+    }
+
+    /// `${maybeEncode(committish)}`
+    pub fn encoded_committish(&self) -> Option<String> {
+        self.committish
+            .filter(|committish| !committish.is_empty())
+            .map(|committish| utf8_percent_encode(committish, URI_COMPONENT).to_string())
+    }
+
+    /// `${maybeEncode(committish) || 'master'}`
+    pub fn encoded_committish_or_master(&self) -> String {
+        self.encoded_committish()
+            .unwrap_or_else(|| "master".to_string())
+    }
+}
+
+// maybeEncode(value)
+pub fn encode(value: &str) -> String {
+    utf8_percent_encode(value, URI_COMPONENT).to_string()
+}
+
+// const maybeJoin = (...args) => args.every(arg => arg) ? args.join('') : ''
+pub fn maybe_join(prefix: &str, value: Option<&str>) -> String {
+    match value {
+        Some(value) if !value.is_empty() => format!("{}{}", prefix, value),
+        _ => String::new(),
+    }
+}
+
+// function formatHashFragment (fragment) {
+//   return fragment.toLowerCase().replace(/^\W+|\/|\W+$/g, '').replace(/\W+/g, '-')
+// }
+pub fn format_hash_fragment(fragment: &str) -> String {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    let fragment = fragment.to_lowercase();
+    let fragment = fragment
+        .trim_start_matches(|c| !is_word(c))
+        .trim_end_matches(|c| !is_word(c))
+        .replace('/', "");
+
+    let mut result = String::with_capacity(fragment.len());
+    let mut in_non_word = false;
+    for c in fragment.chars() {
+        if is_word(c) {
+            result.push(c);
+            in_non_word = false;
+        } else if !in_non_word {
+            result.push('-');
+            in_non_word = true;
+        }
+    }
+    result
 }
 
 impl Debug for dyn Parser {
@@ -49,4 +377,6 @@ pub struct ParsedSegments<'a> {
     pub user: Option<&'a str>,
     pub project: Option<&'a str>,
     pub committish: Option<&'a str>,
+    pub directory: Option<&'a str>,
+    pub filename: Option<&'a str>,
 }
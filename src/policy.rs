@@ -0,0 +1,323 @@
+use crate::ssh::wildcard_match;
+use crate::{DefaultRepresentation, HostedGitInfo, Provider};
+use std::fmt;
+#[cfg(feature = "policy")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "policy")]
+use thiserror::Error;
+
+/// Errors that can occur when loading a [Policy] from a file.
+#[cfg(feature = "policy")]
+#[derive(Debug, Error)]
+pub enum PolicyError {
+    /// Failed to read the policy file.
+    #[error("Failed to read policy file")]
+    Io(#[from] std::io::Error),
+
+    /// Failed to parse a TOML policy.
+    #[error("Failed to parse TOML policy")]
+    Toml(#[from] toml::de::Error),
+
+    /// Failed to parse a JSON policy.
+    #[error("Failed to parse JSON policy")]
+    Json(#[from] serde_json::Error),
+
+    /// The file extension is neither `.toml` nor `.json`.
+    #[error("Failed to detect policy format of {0}")]
+    UnknownFormat(PathBuf),
+}
+
+/// The parts of a [HostedGitInfo] that a [Policy] can restrict.
+#[derive(Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum PolicyField {
+    /// The [shortcut](crate::Provider::shortcut) of the provider, e.g. `github`.
+    Provider,
+    /// The host name as written in the URL, e.g. `www.github.com`, or the
+    /// [domain](crate::Provider::domain) of the provider for shortcuts.
+    Host,
+    /// The scheme, e.g. `https` or `ssh`.
+    ///
+    /// `git+https`, `git+http` and `git+ssh` are normalized to `https`,
+    /// `http` and `ssh`, and
+    /// shortcuts like `github:foo/bar` have the scheme `shortcut`.
+    Scheme,
+    /// The repository path in the format `<owner>/<project>`, or just
+    /// `<project>` for gists without an owner.
+    Repository,
+    /// Embedded credentials in the URL.
+    Credentials,
+}
+
+impl fmt::Display for PolicyField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PolicyField::Provider => "provider",
+            PolicyField::Host => "host",
+            PolicyField::Scheme => "scheme",
+            PolicyField::Repository => "repository",
+            PolicyField::Credentials => "credentials",
+        })
+    }
+}
+
+/// The result of [Policy::evaluate].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Verdict {
+    /// None of the rules denied the URL.
+    Allowed,
+    /// The value matched a pattern of a deny-list.
+    Denied {
+        field: PolicyField,
+        value: String,
+        pattern: String,
+    },
+    /// The field has an allow-list, but the value matched none of its patterns.
+    NotAllowed { field: PolicyField, value: String },
+    /// The URL contains credentials, which are not allowed by the policy.
+    CredentialsNotAllowed,
+}
+
+impl Verdict {
+    /// Whether the URL is allowed by the policy.
+    pub fn is_allowed(&self) -> bool {
+        *self == Verdict::Allowed
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Allowed => write!(f, "allowed"),
+            Verdict::Denied {
+                field,
+                value,
+                pattern,
+            } => write!(f, "{} `{}` is denied by `{}`", field, value, pattern),
+            Verdict::NotAllowed { field, value } => {
+                write!(f, "{} `{}` is not in the allow-list", field, value)
+            }
+            Verdict::CredentialsNotAllowed => write!(f, "credentials are not allowed"),
+        }
+    }
+}
+
+/// Allow- and deny-lists of glob patterns for a single [PolicyField].
+///
+/// Patterns support the `*` and `?` wildcards and are matched
+/// case-insensitively.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct Rules {
+    allow: Vec<String>,
+    deny: Vec<String>,
+}
+
+impl Rules {
+    fn evaluate(&self, field: PolicyField, value: &str) -> Verdict {
+        let value = value.to_ascii_lowercase();
+        let matches = |pattern: &&String| wildcard_match(&pattern.to_ascii_lowercase(), &value);
+
+        if let Some(pattern) = self.deny.iter().find(matches) {
+            return Verdict::Denied {
+                field,
+                value,
+                pattern: pattern.clone(),
+            };
+        }
+
+        if !self.allow.is_empty() && !self.allow.iter().any(|p| matches(&p)) {
+            return Verdict::NotAllowed { field, value };
+        }
+
+        Verdict::Allowed
+    }
+}
+
+/// Restricts which repositories are acceptable, e.g. for dependencies that
+/// are fetched by a build system.
+///
+/// For every field, deny-lists take precedence over allow-lists, and an empty
+/// allow-list allows everything.
+///
+/// ```
+/// use hosted_git_info::{HostedGitInfo, Policy, PolicyField, Verdict};
+///
+/// let mut policy = Policy::new();
+/// policy
+///     .allow(PolicyField::Repository, "my-org/*")
+///     .deny(PolicyField::Scheme, "git")
+///     .allow_credentials(false);
+///
+/// let info = HostedGitInfo::from_url("https://github.com/my-org/project.git").unwrap();
+/// assert_eq!(policy.evaluate(&info), Verdict::Allowed);
+///
+/// let info = HostedGitInfo::from_url("git://github.com/my-org/project.git").unwrap();
+/// assert_eq!(policy.evaluate(&info).to_string(), "scheme `git` is denied by `git`");
+/// ```
+///
+/// With the `policy` feature, policies can also be loaded from TOML or JSON
+/// files:
+///
+/// ```toml
+/// allow_credentials = false
+///
+/// [providers]
+/// allow = ["github", "gitlab"]
+///
+/// [schemes]
+/// allow = ["https", "ssh"]
+///
+/// [repositories]
+/// allow = ["my-org/*"]
+/// deny = ["my-org/legacy-*"]
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct Policy {
+    providers: Rules,
+    hosts: Rules,
+    schemes: Rules,
+    repositories: Rules,
+    allow_credentials: bool,
+}
+
+impl Policy {
+    /// Creates a policy that allows everything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a policy from a `.toml` or `.json` file.
+    #[cfg(feature = "policy")]
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, PolicyError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Self::from_toml(&content),
+            Some("json") => Self::from_json(&content),
+            _ => Err(PolicyError::UnknownFormat(path.to_path_buf())),
+        }
+    }
+
+    /// Parses a policy in TOML format.
+    #[cfg(feature = "policy")]
+    pub fn from_toml(input: &str) -> Result<Self, PolicyError> {
+        Ok(toml::from_str(input)?)
+    }
+
+    /// Parses a policy in JSON format.
+    #[cfg(feature = "policy")]
+    pub fn from_json(input: &str) -> Result<Self, PolicyError> {
+        Ok(serde_json::from_str(input)?)
+    }
+
+    /// Adds a pattern to the allow-list of a field.
+    ///
+    /// Use [Policy::allow_credentials] for [PolicyField::Credentials].
+    pub fn allow<S: Into<String>>(&mut self, field: PolicyField, pattern: S) -> &mut Self {
+        if let Some(rules) = self.rules_mut(field) {
+            rules.allow.push(pattern.into());
+        }
+        self
+    }
+
+    /// Adds a pattern to the deny-list of a field.
+    ///
+    /// Use [Policy::allow_credentials] for [PolicyField::Credentials].
+    pub fn deny<S: Into<String>>(&mut self, field: PolicyField, pattern: S) -> &mut Self {
+        if let Some(rules) = self.rules_mut(field) {
+            rules.deny.push(pattern.into());
+        }
+        self
+    }
+
+    /// Sets whether URLs with embedded credentials are allowed. Defaults to `true`.
+    pub fn allow_credentials(&mut self, allow: bool) -> &mut Self {
+        self.allow_credentials = allow;
+        self
+    }
+
+    fn rules_mut(&mut self, field: PolicyField) -> Option<&mut Rules> {
+        match field {
+            PolicyField::Provider => Some(&mut self.providers),
+            PolicyField::Host => Some(&mut self.hosts),
+            PolicyField::Scheme => Some(&mut self.schemes),
+            PolicyField::Repository => Some(&mut self.repositories),
+            PolicyField::Credentials => None,
+        }
+    }
+
+    /// Evaluates the policy against a parsed URL and returns the first rule
+    /// that denies it, if any.
+    pub fn evaluate(&self, info: &HostedGitInfo) -> Verdict {
+        if !self.allow_credentials && info.auth().is_some() {
+            return Verdict::CredentialsNotAllowed;
+        }
+
+        let repository = match info.user() {
+            Some(user) => format!("{}/{}", user, info.project()),
+            None => info.project().to_string(),
+        };
+
+        let checks = [
+            (
+                PolicyField::Provider,
+                &self.providers,
+                info.provider().shortcut(),
+            ),
+            (
+                PolicyField::Host,
+                &self.hosts,
+                info.host().unwrap_or_else(|| info.provider().domain()),
+            ),
+            (PolicyField::Scheme, &self.schemes, scheme(info)),
+            (PolicyField::Repository, &self.repositories, &repository),
+        ];
+
+        for (field, rules, value) in checks.iter() {
+            let verdict = rules.evaluate(*field, value);
+            if !verdict.is_allowed() {
+                return verdict;
+            }
+        }
+
+        Verdict::Allowed
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy {
+            providers: Rules::default(),
+            hosts: Rules::default(),
+            schemes: Rules::default(),
+            repositories: Rules::default(),
+            allow_credentials: true,
+        }
+    }
+}
+
+fn scheme(info: &HostedGitInfo) -> &str {
+    if let Some(scheme) = info.scheme() {
+        return match scheme {
+            "git+https" => "https",
+            "git+http" => "http",
+            "git+ssh" => "ssh",
+            scheme if Provider::from_shortcut(scheme).is_some() => "shortcut",
+            scheme => scheme,
+        };
+    }
+
+    // values that were built instead of parsed have no original scheme
+    match info.default_representation() {
+        DefaultRepresentation::Shortcut => "shortcut",
+        DefaultRepresentation::Git => "git",
+        DefaultRepresentation::Https => "https",
+        DefaultRepresentation::Ssh => "ssh",
+        DefaultRepresentation::Other => "other",
+    }
+}
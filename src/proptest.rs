@@ -1,4 +1,5 @@
-use crate::HostedGitInfo;
+use crate::config::GitConfig;
+use crate::{CachedParser, HostedGitInfo, SshConfig};
 use proptest::prelude::*;
 
 proptest! {
@@ -43,4 +44,33 @@ proptest! {
     fn doesnt_crash_from_random_bitbucket_url_with_no_protocol(s in "\\PC*@bitbucket.org:\\PC*") {
         HostedGitInfo::from_url(&s);
     }
+
+    #[test]
+    #[allow(unused_must_use)]
+    fn doesnt_crash_from_random_git_config(s in "\\PC*") {
+        GitConfig::parse(&s);
+    }
+
+    #[test]
+    #[allow(unused_must_use)]
+    fn doesnt_crash_from_random_git_config_section(s in "\\[remote \\PC*\n\\PC*") {
+        GitConfig::parse(&s);
+    }
+
+    #[test]
+    #[allow(unused_must_use)]
+    fn doesnt_crash_from_random_ssh_config(s in "\\PC*") {
+        if let Ok(config) = SshConfig::parse(&s) {
+            config.parse_url("git@alias:foo/bar.git");
+        }
+    }
+
+    #[test]
+    fn cached_parser_matches_from_url(inputs in prop::collection::vec("(foo|bar|baz)/[a-d]", 1..50)) {
+        let parser = CachedParser::with_capacity(3);
+        for input in &inputs {
+            prop_assert_eq!(parser.parse(input), HostedGitInfo::from_url(input));
+        }
+        prop_assert!(parser.stats().len() <= 3);
+    }
 }
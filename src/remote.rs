@@ -0,0 +1,130 @@
+use crate::config::{ConfigError, GitConfig};
+use crate::{HostedGitInfo, ParseError};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Enum of the config keys that can hold the URL of a remote.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum RemoteUrlKind {
+    /// `remote.<name>.url`, used for fetching (and pushing if there is no `pushurl`)
+    Url,
+    /// `remote.<name>.pushurl`, used for pushing only
+    PushUrl,
+}
+
+/// A URL of a `[remote "<name>"]` section of a repository's config file.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Remote {
+    name: String,
+    kind: RemoteUrlKind,
+    url: String,
+    info: Result<HostedGitInfo, ParseError>,
+}
+
+impl Remote {
+    /// The name of the remote.
+    ///
+    /// Example: `[remote "origin"]` → `origin`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the URL was read from a `url` or a `pushurl` key.
+    pub fn kind(&self) -> RemoteUrlKind {
+        self.kind
+    }
+
+    /// The URL as written in the config file.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The result of passing [Remote::url] to [HostedGitInfo::from_url].
+    ///
+    /// Remotes that are not hosted on a supported provider return a [ParseError].
+    pub fn info(&self) -> Result<&HostedGitInfo, ParseError> {
+        self.info.as_ref().map_err(|error| *error)
+    }
+}
+
+/// Reads the remotes of the work tree or bare repository at `path`.
+///
+/// `.git` files, as they are used by linked work trees and submodules, are
+/// followed to the actual git directory. All `url` and `pushurl` keys of all
+/// `[remote "<name>"]` sections are returned in the order in which they
+/// appear in the config file.
+///
+/// ```no_run
+/// use hosted_git_info::{read_remotes, Provider};
+///
+/// for remote in read_remotes(".").unwrap() {
+///     if let Ok(info) = remote.info() {
+///         println!("{}: {:?}", remote.name(), info.provider());
+///     }
+/// }
+/// ```
+pub fn read_remotes<P: AsRef<Path>>(path: P) -> Result<Vec<Remote>, ConfigError> {
+    let git_dir = find_git_dir(path.as_ref())?;
+    let config = GitConfig::from_file(&common_dir(&git_dir)?.join("config"))?;
+
+    let remotes = config
+        .entries()
+        .filter(|entry| entry.section == "remote")
+        .filter_map(|entry| {
+            let kind = match entry.key.as_str() {
+                "url" => RemoteUrlKind::Url,
+                "pushurl" => RemoteUrlKind::PushUrl,
+                _ => return None,
+            };
+            let name = entry.subsection.as_ref()?;
+            let url = entry.value.as_ref()?;
+
+            Some(Remote {
+                name: name.clone(),
+                kind,
+                url: url.clone(),
+                info: HostedGitInfo::from_url(url),
+            })
+        })
+        .collect();
+
+    Ok(remotes)
+}
+
+/// Returns the git directory of the work tree or bare repository at `path`.
+pub(crate) fn find_git_dir(path: &Path) -> Result<PathBuf, ConfigError> {
+    let dot_git = path.join(".git");
+    if dot_git.is_dir() {
+        return Ok(dot_git);
+    }
+
+    // linked work trees and submodules contain a `gitdir: <path>` file instead
+    if dot_git.is_file() {
+        let content = fs::read_to_string(&dot_git)?;
+        let git_dir = content
+            .trim_end()
+            .strip_prefix("gitdir:")
+            .map(str::trim_start)
+            .ok_or_else(|| ConfigError::NotARepository(path.to_path_buf()))?;
+
+        return Ok(path.join(git_dir));
+    }
+
+    // bare repositories have no work tree
+    if path.join("HEAD").is_file() {
+        return Ok(path.to_path_buf());
+    }
+
+    Err(ConfigError::NotARepository(path.to_path_buf()))
+}
+
+/// Returns the directory containing the shared `config` file of a git
+/// directory, which differs from the git directory for linked work trees.
+pub(crate) fn common_dir(git_dir: &Path) -> Result<PathBuf, ConfigError> {
+    match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(content) => Ok(git_dir.join(content.trim_end())),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(git_dir.to_path_buf()),
+        Err(error) => Err(error.into()),
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{HostedGitInfo, Provider};
+use std::fmt;
+
+/// The canonical identity of a repository, independent of how its URL was
+/// written.
+///
+/// Two [HostedGitInfo] values with the same [RepoId] point to the same
+/// repository, even if they differ in representation, authentication or
+/// committish. Owner and project names are normalized according to the
+/// rules of each provider:
+///
+/// - GitHub, GitLab and Bitbucket names are case-insensitive
+/// - Gists are identified by their ID alone, the user is ignored
+///
+/// ```
+/// use hosted_git_info::HostedGitInfo;
+///
+/// let a = HostedGitInfo::from_url("git@github.com:Foo/Bar.git").unwrap();
+/// let b = HostedGitInfo::from_url("https://github.com/foo/bar#main").unwrap();
+/// assert_ne!(a, b);
+/// assert_eq!(a.repo_id(), b.repo_id());
+/// assert_eq!(a.repo_id().to_string(), "github.com/foo/bar");
+/// ```
+#[derive(Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Clone)]
+pub struct RepoId {
+    provider: Provider,
+    host: String,
+    owner: Option<String>,
+    project: String,
+}
+
+impl RepoId {
+    pub(crate) fn new(info: &HostedGitInfo) -> Self {
+        let provider = info.provider();
+
+        let owner = match provider {
+            Provider::Gist => None,
+            _ => info.user(),
+        };
+
+        RepoId {
+            provider,
+            host: provider.domain().to_string(),
+            owner: owner.map(str::to_lowercase),
+            project: info.project().to_lowercase(),
+        }
+    }
+
+    /// The hosting provider of the repository.
+    pub fn provider(&self) -> Provider {
+        self.provider
+    }
+
+    /// The host name of the provider, without any `www.` prefix.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// The normalized user or organization, if the provider scopes
+    /// repositories by owner.
+    pub fn owner(&self) -> Option<&str> {
+        self.owner.as_deref()
+    }
+
+    /// The normalized name of the project.
+    pub fn project(&self) -> &str {
+        &self.project
+    }
+}
+
+impl fmt::Display for RepoId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.owner {
+            Some(owner) => write!(f, "{}/{}/{}", self.host, owner, self.project),
+            None => write!(f, "{}/{}", self.host, self.project),
+        }
+    }
+}
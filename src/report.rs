@@ -0,0 +1,409 @@
+use crate::auth::userinfo_range;
+use crate::lint::lookalike;
+use crate::suggest::{closest_domain, closest_shortcut};
+use crate::{is_github_shorthand, Auth, Leniency, ParseError, Provider};
+use std::fmt;
+use std::ops::Range;
+
+/// A [ParseError] together with the input that caused it, the byte span of
+/// the problematic part of the input and an optional suggestion.
+///
+/// The [render](ParseReport::render) method formats the report with the
+/// offending characters underlined:
+///
+/// ```
+/// use hosted_git_info::HostedGitInfo;
+///
+/// let input = "https://gitlab.corn/foo/bar";
+/// let report = HostedGitInfo::from_url(input).unwrap_err().report(input);
+/// assert_eq!(report.span(), 8..19);
+/// assert_eq!(report.suggestion(), Some("gitlab.com"));
+/// assert_eq!(
+///     report.render(),
+///     "\
+/// error: Failed to recognize URL
+///   |
+///   | https://gitlab.corn/foo/bar
+///   |         ^^^^^^^^^^^ unknown host
+///   |
+///   = help: did you mean `gitlab.com`?
+/// "
+/// );
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ParseReport {
+    error: ParseError,
+    input: String,
+    span: Range<usize>,
+    label: String,
+    suggestion: Option<String>,
+}
+
+impl ParseError {
+    /// Locates the cause of the error in the input that failed to parse.
+    pub fn report(self, giturl: &str) -> ParseReport {
+        ParseReport::new(self, giturl)
+    }
+}
+
+impl ParseReport {
+    /// Locates the cause of the error in the input that failed to parse.
+    ///
+    /// Credentials in the input are redacted like in the [Display](fmt::Display)
+    /// output of [Auth].
+    pub fn new(error: ParseError, giturl: &str) -> Self {
+        let whole = 0..giturl.len();
+
+        // the parser records where it failed, which only applies if it fails
+        // with the same error for this input
+        let (traced_error, trace) = crate::trace(giturl);
+        let failure = trace.failure.filter(|_| traced_error == Some(error));
+
+        let (span, kind) = match error {
+            ParseError::InvalidUrl(error) => {
+                use url::ParseError::*;
+
+                // the URL parser does not report where the error is, so the
+                // host and port are located with the same heuristics as
+                // `parse()`
+                let layout = Layout::new(giturl);
+                let (span, label) = match error {
+                    InvalidPort => (layout.port.or(layout.host), "invalid port"),
+                    EmptyHost => (layout.host, "missing host"),
+                    IdnaError
+                    | InvalidIpv4Address
+                    | InvalidIpv6Address
+                    | InvalidDomainCharacter => (layout.host, "invalid host"),
+                    _ => (None, "invalid URL"),
+                };
+                (span.unwrap_or(whole), Kind::InvalidUrl(label))
+            }
+            ParseError::InvalidUriEncoding(_) | ParseError::UnknownUrl => match failure {
+                // hosts that are not ASCII are converted to punycode by the
+                // URL parser, and can not be found in the input
+                Some((Cause::UnknownHost, None)) => {
+                    let span = Layout::new(giturl).host.unwrap_or(whole);
+                    (span, Kind::Failure(Cause::UnknownHost))
+                }
+                Some((cause, span)) => (span.unwrap_or(whole), Kind::Failure(cause)),
+                None => (whole, Kind::Unrecognized(error)),
+            },
+            ParseError::RequiresLeniency(leniency) => {
+                let span = trace
+                    .required
+                    .into_iter()
+                    .find(|(required, _)| *required == leniency)
+                    .map_or(whole, |(_, span)| span);
+                (span, Kind::Leniency(leniency))
+            }
+        };
+
+        let redaction = Redaction::new(giturl);
+        let input = redaction.apply(giturl);
+        let span = redaction.map(span);
+        let text = &input[span.clone()];
+
+        let (label, suggestion) = match kind {
+            Kind::InvalidUrl(label) => (label.to_string(), None),
+            Kind::Unrecognized(ParseError::InvalidUriEncoding(_))
+            | Kind::Failure(Cause::Encoding) => ("invalid percent-encoding".to_string(), None),
+            Kind::Unrecognized(_) => ("unrecognized URL".to_string(), None),
+            Kind::Failure(Cause::UnknownHost) => {
+                let name = text.to_lowercase();
+                let suggestion = lookalike(&name)
+                    .or_else(|| closest_domain(&name).map(|(provider, _)| provider))
+                    .map(|provider| provider.domain().to_string());
+                ("unknown host".to_string(), suggestion)
+            }
+            Kind::Failure(Cause::UnknownProvider) => {
+                let name = text.to_lowercase();
+                let suggestion = closest_shortcut(&name).map(|(provider, _)| provider.shortcut());
+                (
+                    "unknown provider".to_string(),
+                    suggestion.map(str::to_string),
+                )
+            }
+            Kind::Failure(Cause::UnsupportedScheme(provider)) => {
+                let label = format!("scheme not supported by {}", provider.display_name());
+                let suggestion = secure_scheme(&text.to_lowercase())
+                    .filter(|scheme| provider.supported_schemes().contains(scheme))
+                    .map(str::to_string);
+                (label, suggestion)
+            }
+            Kind::Failure(Cause::Path) => ("expected `<user>/<project>`".to_string(), None),
+            Kind::Leniency(Leniency::GitHubShorthand) => (
+                "GitHub shorthand".to_string(),
+                Some(format!("github:{}", text)),
+            ),
+            Kind::Leniency(Leniency::ScpSyntax) => {
+                // only `[user@]host:` is corrected to a `git+ssh://` URL, and
+                // not a scheme that is missing its slashes
+                let suggestion = text
+                    .strip_suffix(':')
+                    .filter(|host| host.contains('@') || host.contains('.'))
+                    .map(|host| format!("git+ssh://{}/", host));
+                ("scp-style syntax".to_string(), suggestion)
+            }
+            Kind::Leniency(Leniency::InsecureScheme) => {
+                let suggestion = secure_scheme(text).map(str::to_string);
+                ("insecure scheme".to_string(), suggestion)
+            }
+        };
+
+        ParseReport {
+            error,
+            input,
+            span,
+            label,
+            suggestion,
+        }
+    }
+
+    /// The underlying error.
+    pub fn error(&self) -> ParseError {
+        self.error
+    }
+
+    /// The input that failed to parse, with credentials redacted.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// The byte range of the problematic part of the [input](ParseReport::input).
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// A short description of the problematic part, e.g. `unknown host`.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// A replacement for the [span](ParseReport::span) that would probably
+    /// fix the error, e.g. `gitlab.com` for `gitlab.corn`.
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+
+    /// Renders the report over multiple lines, with the span of the input
+    /// underlined.
+    pub fn render(&self) -> String {
+        // columns are counted in characters, so that the underline lines up
+        // for non-ASCII input
+        let start = self.input[..self.span.start].chars().count();
+        let width = self.input[self.span.clone()].chars().count().max(1);
+
+        let mut output = format!("error: {}\n", self.error);
+        output += "  |\n";
+        output += &format!("  | {}\n", self.input);
+        output += &format!(
+            "  | {}{} {}\n",
+            " ".repeat(start),
+            "^".repeat(width),
+            self.label
+        );
+        if let Some(suggestion) = &self.suggestion {
+            output += "  |\n";
+            output += &format!("  = help: did you mean `{}`?\n", suggestion);
+        }
+        output
+    }
+}
+
+impl fmt::Display for ParseReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} at {}..{}",
+            self.error, self.label, self.span.start, self.span.end
+        )
+    }
+}
+
+impl std::error::Error for ParseReport {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Where the parser failed, and which heuristics it needed to parse the
+/// input, as byte ranges of the input.
+#[derive(Debug, Default)]
+pub(crate) struct Trace {
+    pub(crate) required: Vec<(Leniency, Range<usize>)>,
+    pub(crate) failure: Option<(Cause, Option<Range<usize>>)>,
+}
+
+impl Trace {
+    pub(crate) fn require(&mut self, leniency: Leniency, span: Range<usize>) {
+        self.required.push((leniency, span));
+    }
+
+    pub(crate) fn fail(&mut self, cause: Cause, span: Option<Range<usize>>) {
+        self.failure = Some((cause, span));
+    }
+}
+
+/// The part of the input that caused the parser to fail.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub(crate) enum Cause {
+    UnknownHost,
+    UnknownProvider,
+    UnsupportedScheme(Provider),
+    Path,
+    Encoding,
+}
+
+// how the span of a report is labeled
+enum Kind {
+    InvalidUrl(&'static str),
+    Unrecognized(ParseError),
+    Failure(Cause),
+    Leniency(Leniency),
+}
+
+// the first occurrence of a part of the parsed URL in the input after the
+// userinfo, ignoring the case of ASCII characters, which the URL parser
+// normalizes
+pub(crate) fn find_span(input: &str, value: &str) -> Option<Range<usize>> {
+    let offset = userinfo_range(input).map_or(0, |range| range.end);
+    input[offset..]
+        .to_ascii_lowercase()
+        .find(&value.to_ascii_lowercase())
+        .map(|start| offset + start..offset + start + value.len())
+}
+
+// the scheme of the parsed URL, if the input starts with it
+pub(crate) fn scheme_span(input: &str, scheme: &str) -> Option<Range<usize>> {
+    let prefix = input.get(..scheme.len())?;
+    if prefix.eq_ignore_ascii_case(scheme) && input[scheme.len()..].starts_with(':') {
+        Some(0..scheme.len())
+    } else {
+        None
+    }
+}
+
+// the path of the parsed URL at the end of the input (before the fragment),
+// which starts with `:` instead of `/` for scp-style input
+pub(crate) fn path_span(input: &str, path: &str) -> Option<Range<usize>> {
+    let body = &input[..input.find('#').unwrap_or(input.len())];
+    [path, path.trim_start_matches('/')]
+        .iter()
+        .find(|path| !path.is_empty() && body.ends_with(*path))
+        .map(|path| body.len() - path.len()..body.len())
+}
+
+// the userinfo of the input and its redacted replacement
+struct Redaction {
+    userinfo: Option<(Range<usize>, String)>,
+}
+
+impl Redaction {
+    fn new(input: &str) -> Self {
+        let userinfo = userinfo_range(input).map(|range| {
+            let redacted = Auth::from(&input[range.clone()]).to_string();
+            (range, redacted)
+        });
+        Redaction { userinfo }
+    }
+
+    fn apply(&self, input: &str) -> String {
+        match &self.userinfo {
+            Some((range, redacted)) => format!(
+                "{}{}{}",
+                &input[..range.start],
+                redacted,
+                &input[range.end..]
+            ),
+            None => input.to_string(),
+        }
+    }
+
+    // moves a span of the input to the same part of the redacted input
+    fn map(&self, span: Range<usize>) -> Range<usize> {
+        let (range, redacted) = match &self.userinfo {
+            Some(userinfo) => userinfo,
+            None => return span,
+        };
+
+        let offset = |index: usize, is_end: bool| {
+            if index <= range.start {
+                index
+            } else if index >= range.end {
+                index - range.end + range.start + redacted.len()
+            } else if is_end {
+                range.start + redacted.len()
+            } else {
+                range.start
+            }
+        };
+        offset(span.start, false)..offset(span.end, true)
+    }
+}
+
+// the byte ranges of the parts of a (possibly invalid) URL, based on the
+// same heuristics that `parse()` uses
+#[derive(Debug, Default)]
+pub(crate) struct Layout {
+    pub(crate) scheme: Option<Range<usize>>,
+    pub(crate) host: Option<Range<usize>>,
+    pub(crate) port: Option<Range<usize>>,
+}
+
+impl Layout {
+    pub(crate) fn new(input: &str) -> Self {
+        let body_end = input.find('#').unwrap_or(input.len());
+        let body = &input[..body_end];
+        let mut layout = Layout::default();
+
+        if is_github_shorthand(input) {
+            return layout;
+        }
+
+        if let Some(index) = body.find("://") {
+            layout.scheme = Some(0..index);
+            let start = index + 3;
+            let end = body[start..].find('/').map_or(body_end, |i| start + i);
+            layout.authority(body, start..end);
+            return layout;
+        }
+
+        if let Some(colon) = body.find(':') {
+            let before = &body[..colon];
+            if before.contains('@') || before.contains('.') {
+                // scp-style `[user@]host:path`
+                layout.authority(body, 0..colon);
+            } else {
+                layout.scheme = Some(0..colon);
+            }
+        }
+
+        layout
+    }
+
+    fn authority(&mut self, body: &str, range: Range<usize>) {
+        let authority = &body[range.clone()];
+        let host_start = match authority.rfind('@') {
+            Some(at) => range.start + at + 1,
+            None => range.start,
+        };
+
+        let host = &body[host_start..range.end];
+        match host.rfind(':') {
+            Some(colon) if !host.ends_with(']') => {
+                self.host = Some(host_start..host_start + colon);
+                self.port = Some(host_start + colon + 1..range.end);
+            }
+            _ => self.host = Some(host_start..range.end),
+        }
+    }
+}
+
+fn secure_scheme(scheme: &str) -> Option<&'static str> {
+    match scheme {
+        "http" => Some("https"),
+        "git+http" => Some("git+https"),
+        "git" => Some("git+https"),
+        _ => None,
+    }
+}
@@ -0,0 +1,212 @@
+use crate::config::{ConfigError, GitConfig, GitConfigFiles};
+use crate::{HostedGitInfo, ParseError};
+use std::borrow::Cow;
+use std::io;
+use std::path::Path;
+
+/// A single `[url "<base>"] insteadOf = <prefix>` rule.
+#[derive(Debug, Eq, PartialEq, Clone)]
+struct Rule {
+    base: String,
+    prefix: String,
+}
+
+/// Applies git's [`url.<base>.insteadOf`](https://git-scm.com/docs/git-config#Documentation/git-config.txt-urlltbasegtinsteadOf)
+/// and `url.<base>.pushInsteadOf` rewrites to URLs before they are parsed.
+///
+/// If multiple prefixes match a URL, the longest one wins, just like in git.
+///
+/// ```
+/// use hosted_git_info::{Provider, UrlRewriter};
+///
+/// let mut rewriter = UrlRewriter::new();
+/// rewriter.add_instead_of("https://gitlab.com/", "https://mirror.example.com/");
+///
+/// let url = rewriter.parse("https://mirror.example.com/foo/bar.git");
+/// assert!(url.original_info().is_err());
+/// assert_eq!(url.rewritten(), "https://gitlab.com/foo/bar.git");
+/// assert_eq!(url.info().unwrap().provider(), Provider::GitLab);
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct UrlRewriter {
+    instead_of: Vec<Rule>,
+    push_instead_of: Vec<Rule>,
+}
+
+impl UrlRewriter {
+    /// Creates a rewriter without any rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a rewriter with the rules of the system and global git config
+    /// files and, if `repository` is given, the config file of that work tree
+    /// or bare repository.
+    ///
+    /// Config files that do not exist are skipped.
+    pub fn from_git_config<P: AsRef<Path>>(repository: Option<P>) -> Result<Self, ConfigError> {
+        Self::from_config_files(&GitConfigFiles::from_env(), repository)
+    }
+
+    /// Like [UrlRewriter::from_git_config], but reads the given system and
+    /// global config files instead of the ones that git would read.
+    pub fn from_config_files<P: AsRef<Path>>(
+        files: &GitConfigFiles,
+        repository: Option<P>,
+    ) -> Result<Self, ConfigError> {
+        let repository = repository.as_ref().map(|path| path.as_ref());
+
+        let mut rewriter = Self::new();
+        for path in files.with_repository(repository)? {
+            match GitConfig::from_file(&path) {
+                Ok(config) => rewriter.add_config(&config),
+                Err(ConfigError::Io(error)) if error.kind() == io::ErrorKind::NotFound => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(rewriter)
+    }
+
+    /// Adds the rules of an explicitly supplied git config file.
+    pub fn add_file<P: AsRef<Path>>(&mut self, path: P) -> Result<&mut Self, ConfigError> {
+        let config = GitConfig::from_file(path.as_ref())?;
+        self.add_config(&config);
+        Ok(self)
+    }
+
+    /// Adds a `[url "<base>"] insteadOf = <prefix>` rule.
+    pub fn add_instead_of<B: Into<String>, P: Into<String>>(
+        &mut self,
+        base: B,
+        prefix: P,
+    ) -> &mut Self {
+        self.instead_of.push(Rule {
+            base: base.into(),
+            prefix: prefix.into(),
+        });
+        self
+    }
+
+    /// Adds a `[url "<base>"] pushInsteadOf = <prefix>` rule.
+    pub fn add_push_instead_of<B: Into<String>, P: Into<String>>(
+        &mut self,
+        base: B,
+        prefix: P,
+    ) -> &mut Self {
+        self.push_instead_of.push(Rule {
+            base: base.into(),
+            prefix: prefix.into(),
+        });
+        self
+    }
+
+    fn add_config(&mut self, config: &GitConfig) {
+        for entry in config.entries() {
+            if entry.section != "url" {
+                continue;
+            }
+
+            let (base, prefix) = match (&entry.subsection, &entry.value) {
+                (Some(base), Some(prefix)) => (base, prefix),
+                _ => continue,
+            };
+
+            match entry.key.as_str() {
+                "insteadof" => self.add_instead_of(base.as_str(), prefix.as_str()),
+                "pushinsteadof" => self.add_push_instead_of(base.as_str(), prefix.as_str()),
+                _ => continue,
+            };
+        }
+    }
+
+    /// Returns the URL that git would fetch from.
+    pub fn rewrite<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        rewrite_with(&self.instead_of, url).unwrap_or(Cow::Borrowed(url))
+    }
+
+    /// Returns the URL that git would push to.
+    ///
+    /// `pushInsteadOf` rules take precedence, but if none of them match, the
+    /// `insteadOf` rules are applied instead.
+    pub fn rewrite_push<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        rewrite_with(&self.push_instead_of, url).unwrap_or_else(|| self.rewrite(url))
+    }
+
+    /// Rewrites the URL with [UrlRewriter::rewrite] and parses both the
+    /// original and the rewritten URL.
+    pub fn parse(&self, url: &str) -> RewrittenUrl {
+        RewrittenUrl::new(url, self.rewrite(url))
+    }
+
+    /// Rewrites the URL with [UrlRewriter::rewrite_push] and parses both the
+    /// original and the rewritten URL.
+    pub fn parse_push(&self, url: &str) -> RewrittenUrl {
+        RewrittenUrl::new(url, self.rewrite_push(url))
+    }
+}
+
+// see `alias_url()` in git's `remote.c`
+fn rewrite_with<'a>(rules: &[Rule], url: &str) -> Option<Cow<'a, str>> {
+    let mut longest: Option<&Rule> = None;
+    for rule in rules {
+        let is_longer = longest.map_or(true, |longest| rule.prefix.len() > longest.prefix.len());
+        if is_longer && url.starts_with(&rule.prefix) {
+            longest = Some(rule);
+        }
+    }
+
+    longest.map(|rule| Cow::Owned(format!("{}{}", rule.base, &url[rule.prefix.len()..])))
+}
+
+/// The result of [UrlRewriter::parse] and [UrlRewriter::parse_push].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct RewrittenUrl {
+    original: String,
+    rewritten: String,
+    original_info: Result<HostedGitInfo, ParseError>,
+    info: Result<HostedGitInfo, ParseError>,
+}
+
+impl RewrittenUrl {
+    fn new(original: &str, rewritten: Cow<'_, str>) -> Self {
+        let original_info = HostedGitInfo::from_url(original);
+        let info = match rewritten {
+            Cow::Borrowed(_) => original_info.clone(),
+            Cow::Owned(ref rewritten) => HostedGitInfo::from_url(rewritten),
+        };
+
+        RewrittenUrl {
+            original: original.to_string(),
+            rewritten: rewritten.into_owned(),
+            original_info,
+            info,
+        }
+    }
+
+    /// The URL before any rules were applied.
+    pub fn original(&self) -> &str {
+        &self.original
+    }
+
+    /// The URL after the matching rule was applied.
+    pub fn rewritten(&self) -> &str {
+        &self.rewritten
+    }
+
+    /// Whether any rule matched the original URL.
+    pub fn is_rewritten(&self) -> bool {
+        self.original != self.rewritten
+    }
+
+    /// The result of parsing [RewrittenUrl::original].
+    pub fn original_info(&self) -> Result<&HostedGitInfo, ParseError> {
+        self.original_info.as_ref().map_err(|error| *error)
+    }
+
+    /// The result of parsing [RewrittenUrl::rewritten], which is the URL that
+    /// git actually contacts.
+    pub fn info(&self) -> Result<&HostedGitInfo, ParseError> {
+        self.info.as_ref().map_err(|error| *error)
+    }
+}
@@ -0,0 +1,91 @@
+//! Helper module to (de)serialize values as strings, using their
+//! [Display](std::fmt::Display) and [FromStr](std::str::FromStr)
+//! implementations.
+//!
+//! ```
+//! use hosted_git_info::HostedGitInfo;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Dependency {
+//!     #[serde(with = "hosted_git_info::serde_string")]
+//!     repository: HostedGitInfo,
+//!     #[serde(with = "hosted_git_info::serde_string::option")]
+//!     fork: Option<HostedGitInfo>,
+//! }
+//!
+//! let json = r#"{ "repository": "github:foo/bar#main", "fork": null }"#;
+//! let dependency: Dependency = serde_json::from_str(json).unwrap();
+//! assert_eq!(dependency.repository.project(), "bar");
+//! assert!(dependency.fork.is_none());
+//!
+//! let json = r#"{ "repository": "https://example.com/foo/bar", "fork": null }"#;
+//! let error = serde_json::from_str::<Dependency>(json).err().unwrap();
+//! assert!(error.to_string().starts_with("Failed to recognize URL"));
+//! ```
+//!
+//! Note that [HostedGitInfo](crate::HostedGitInfo) values do not always
+//! survive the round trip through a string unchanged: the
+//! [Display](crate::HostedGitInfo#impl-Display) implementation omits the
+//! credentials, and URLs with the [Other](crate::DefaultRepresentation::Other)
+//! representation are written as `git+ssh://` URLs, which are parsed back
+//! with the [Ssh](crate::DefaultRepresentation::Ssh) representation. Use the
+//! structured representation if these need to be preserved.
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Serializes a value as a string, see the [module docs](self).
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
+/// Deserializes a value from a string, see the [module docs](self).
+///
+/// Parsing errors (e.g. [ParseError](crate::ParseError)) are reported with
+/// their [Display] message.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromStr,
+    T::Err: Display,
+    D: Deserializer<'de>,
+{
+    let string = String::deserialize(deserializer)?;
+    string.parse().map_err(D::Error::custom)
+}
+
+/// The same as the [parent module](super), but for optional values.
+pub mod option {
+    use super::*;
+
+    /// Serializes an optional value as a string or `None`.
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Display,
+        S: Serializer,
+    {
+        match value {
+            Some(value) => serializer.collect_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserializes an optional value from a string or `None`.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        let string = Option::<String>::deserialize(deserializer)?;
+        string
+            .map(|string| string.parse().map_err(D::Error::custom))
+            .transpose()
+    }
+}
@@ -0,0 +1,506 @@
+use crate::config::ConfigError;
+use crate::parser::{self, Template};
+use crate::{correct_protocol, is_github_shorthand, parse_git_url};
+use crate::{HostedGitInfo, ParseError};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A `Host` (or `Match`) block and the options that follow it.
+#[derive(Debug, Eq, PartialEq, Clone)]
+struct Block {
+    /// `None` for `Match` blocks, which never match because their criteria
+    /// are not evaluated
+    patterns: Option<Vec<String>>,
+    /// lowercased keyword and the (unquoted) arguments
+    options: Vec<(String, String)>,
+}
+
+impl Block {
+    fn matches(&self, host: &str) -> bool {
+        let patterns = match &self.patterns {
+            Some(patterns) => patterns,
+            None => return false,
+        };
+
+        let mut matched = false;
+        for pattern in patterns {
+            if let Some(pattern) = pattern.strip_prefix('!') {
+                if wildcard_match(pattern, host) {
+                    return false;
+                }
+            } else if wildcard_match(pattern, host) {
+                matched = true;
+            }
+        }
+        matched
+    }
+}
+
+/// A reader for OpenSSH [`ssh_config`](https://man.openbsd.org/ssh_config)
+/// files, used to resolve `Host` aliases like `github-work` to the real
+/// host name before the provider is detected.
+///
+/// Only the `Host`, `HostName`, `User`, `Port` and `Include` keywords are
+/// evaluated. `Match` blocks are skipped.
+///
+/// ```
+/// use hosted_git_info::{Provider, SshConfig};
+///
+/// let config = SshConfig::parse("Host github-work\n  HostName github.com\n").unwrap();
+///
+/// let url = config.parse_url("git@github-work:org/repo.git").unwrap();
+/// assert_eq!(url.info().provider(), Provider::GitHub);
+/// assert_eq!(url.alias(), Some("github-work"));
+/// assert_eq!(url.ssh(), "git@github-work:org/repo.git");
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct SshConfig {
+    blocks: Vec<Block>,
+}
+
+/// The options that apply to a particular SSH host alias.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct SshHost {
+    alias: String,
+    host_name: String,
+    user: Option<String>,
+    port: Option<u16>,
+}
+
+impl SshHost {
+    /// The host as written in the URL.
+    pub fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    /// The real host that SSH connects to, which is the alias itself if no
+    /// `HostName` option applies.
+    pub fn host_name(&self) -> &str {
+        &self.host_name
+    }
+
+    /// The `User` option, if it applies.
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    /// The `Port` option, if it applies.
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+}
+
+impl SshConfig {
+    /// Creates a config without any `Host` blocks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the user config (`~/.ssh/config`) and the system config
+    /// (`/etc/ssh/ssh_config`), in that order of precedence.
+    ///
+    /// Config files that do not exist are skipped.
+    pub fn from_default_files() -> Result<Self, ConfigError> {
+        let mut files = Vec::new();
+        if let Some(home) = env::var_os("HOME") {
+            files.push(PathBuf::from(home).join(".ssh").join("config"));
+        }
+        files.push(PathBuf::from("/etc/ssh/ssh_config"));
+
+        let mut config = Self::new();
+        for path in files {
+            match Self::from_file(&path) {
+                Ok(other) => config.blocks.extend(other.blocks),
+                Err(ConfigError::Io(error)) if error.kind() == io::ErrorKind::NotFound => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Reads a config file, including all files referenced by `Include`
+    /// keywords in it.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let mut config = Self::new();
+        config.read(path, path.parent(), Some(vec!["*".to_string()]), 0)?;
+        Ok(config)
+    }
+
+    /// Parses the content of a config file.
+    ///
+    /// `Include` keywords with relative paths are resolved against `~/.ssh`.
+    pub fn parse(input: &str) -> Result<Self, ConfigError> {
+        let mut config = Self::new();
+        config.parse_into(input, None, Some(vec!["*".to_string()]), 0)?;
+        Ok(config)
+    }
+
+    fn read(
+        &mut self,
+        path: &Path,
+        base_dir: Option<&Path>,
+        patterns: Option<Vec<String>>,
+        depth: usize,
+    ) -> Result<(), ConfigError> {
+        // see `READCONF_MAX_DEPTH` in OpenSSH's `readconf.c`
+        if depth > 16 {
+            return Err(ConfigError::IncludeDepth(path.to_path_buf()));
+        }
+
+        let input = fs::read_to_string(path)?;
+        self.parse_into(&input, base_dir, patterns, depth)
+    }
+
+    fn parse_into(
+        &mut self,
+        input: &str,
+        base_dir: Option<&Path>,
+        patterns: Option<Vec<String>>,
+        depth: usize,
+    ) -> Result<(), ConfigError> {
+        self.blocks.push(Block {
+            patterns,
+            options: Vec::new(),
+        });
+
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let keyword_end = line
+                .find(|c: char| c.is_whitespace() || c == '=')
+                .unwrap_or(line.len());
+            let keyword = line[..keyword_end].to_ascii_lowercase();
+            let rest = line[keyword_end..].trim_start();
+            let rest = rest.strip_prefix('=').unwrap_or(rest);
+            let args = split_args(rest).ok_or(ConfigError::Syntax { line: index + 1 })?;
+
+            match keyword.as_str() {
+                "host" => self.blocks.push(Block {
+                    patterns: Some(args.iter().map(|arg| arg.to_ascii_lowercase()).collect()),
+                    options: Vec::new(),
+                }),
+                "match" => {
+                    let patterns = match args.first().map(|arg| arg.to_ascii_lowercase()) {
+                        Some(ref arg) if arg == "all" => Some(vec!["*".to_string()]),
+                        _ => None,
+                    };
+                    self.blocks.push(Block {
+                        patterns,
+                        options: Vec::new(),
+                    })
+                }
+                "include" => {
+                    let current = self.blocks.last().and_then(|block| block.patterns.clone());
+                    for arg in &args {
+                        for path in expand_include(base_dir, arg)? {
+                            self.read(&path, base_dir, current.clone(), depth + 1)?;
+                        }
+                    }
+                    // the rest of the including block continues after the included files
+                    self.blocks.push(Block {
+                        patterns: current,
+                        options: Vec::new(),
+                    });
+                }
+                _ => {
+                    let value = args.join(" ");
+                    if let Some(block) = self.blocks.last_mut() {
+                        block.options.push((keyword, value));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the options that apply to the given host alias.
+    ///
+    /// Like in OpenSSH, the first obtained value of each option is used.
+    pub fn resolve(&self, alias: &str) -> SshHost {
+        let lowercase_alias = alias.to_ascii_lowercase();
+
+        let mut host_name = None;
+        let mut user = None;
+        let mut port = None;
+        for block in self
+            .blocks
+            .iter()
+            .filter(|block| block.matches(&lowercase_alias))
+        {
+            for (keyword, value) in &block.options {
+                match keyword.as_str() {
+                    "hostname" if host_name.is_none() => {
+                        host_name = Some(expand_tokens(value, alias))
+                    }
+                    "user" if user.is_none() => user = Some(value.clone()),
+                    "port" if port.is_none() => port = value.parse().ok(),
+                    _ => {}
+                }
+            }
+        }
+
+        SshHost {
+            alias: alias.to_string(),
+            host_name: host_name.unwrap_or_else(|| alias.to_string()),
+            user,
+            port,
+        }
+    }
+
+    /// Parses a URL like [HostedGitInfo::from_url], but replaces unknown SSH
+    /// hosts with their `HostName` option before the provider is detected.
+    pub fn parse_url(&self, giturl: &str) -> Result<ResolvedSshUrl, ParseError> {
+        if is_github_shorthand(giturl) {
+            return HostedGitInfo::from_url(giturl).map(ResolvedSshUrl::unresolved);
+        }
+
+        let mut parsed = parse_git_url(&correct_protocol(giturl))?;
+        let alias = match parsed.host_str() {
+            Some(host) if matches!(parsed.scheme(), "ssh" | "git+ssh") => host.to_string(),
+            _ => return HostedGitInfo::from_url(giturl).map(ResolvedSshUrl::unresolved),
+        };
+
+        let simplified_domain = alias.strip_prefix("www.").unwrap_or(&alias);
+        if parser::parser_from_domain(simplified_domain).is_some() {
+            return HostedGitInfo::from_url(giturl).map(ResolvedSshUrl::unresolved);
+        }
+
+        let host = self.resolve(&alias);
+        if host.host_name == alias {
+            return Err(ParseError::UnknownUrl);
+        }
+
+        // like in OpenSSH, the user in the URL takes precedence over the
+        // `User` option
+        let user = Some(parsed.username())
+            .filter(|user| !user.is_empty())
+            .or_else(|| host.user())
+            .map(str::to_string);
+
+        parsed.set_host(Some(&host.host_name))?;
+        let info = HostedGitInfo::from_url(parsed.as_str())?;
+
+        Ok(ResolvedSshUrl {
+            info,
+            host: Some(host),
+            user,
+        })
+    }
+}
+
+/// The result of [SshConfig::parse_url].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ResolvedSshUrl {
+    info: HostedGitInfo,
+    host: Option<SshHost>,
+    user: Option<String>,
+}
+
+impl ResolvedSshUrl {
+    fn unresolved(info: HostedGitInfo) -> Self {
+        ResolvedSshUrl {
+            info,
+            host: None,
+            user: None,
+        }
+    }
+
+    /// The parsed URL, using the real host name.
+    pub fn info(&self) -> &HostedGitInfo {
+        &self.info
+    }
+
+    /// The SSH options of the alias, if the URL used one.
+    pub fn host(&self) -> Option<&SshHost> {
+        self.host.as_ref()
+    }
+
+    /// The SSH host alias, if the URL used one.
+    pub fn alias(&self) -> Option<&str> {
+        self.host.as_ref().map(SshHost::alias)
+    }
+
+    /// The login user for the alias, which is the user in the URL or
+    /// otherwise the `User` option. `None` if the URL used no alias.
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    /// Regenerates the SSH URL, still pointing to the alias and using the
+    /// same login user if the original URL used an alias.
+    ///
+    /// The scp-style syntax can not contain a port, so a URL with an explicit
+    /// port is regenerated as a `git+ssh://` URL instead.
+    ///
+    /// Example: `git@github-work:org/repo.git#main`
+    pub fn ssh(&self) -> String {
+        let parser = parser::parser_from_provider(self.info.provider());
+        let port = self.alias().and(self.info.port());
+        let template = Template {
+            ssh_user: self.user(),
+            ssh_host: self.alias(),
+            ssh_port: port,
+            ..self.info.template()
+        };
+
+        match port {
+            Some(_) => parser.sshurl(&template),
+            None => parser.ssh(&template),
+        }
+    }
+}
+
+// splits the arguments of a line, respecting double quotes
+fn split_args(input: &str) -> Option<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quoted = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_arg = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if quoted {
+        return None;
+    }
+    if in_arg {
+        args.push(current);
+    }
+
+    Some(args)
+}
+
+// supports `*` and `?` wildcards, see `match_pattern()` in OpenSSH's `match.c`
+pub(crate) fn wildcard_match(pattern: &str, input: &str) -> bool {
+    let pattern = pattern.as_bytes();
+    let input = input.as_bytes();
+
+    let (mut p, mut i) = (0, 0);
+    let mut backtrack = None;
+    while i < input.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, i));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == input[i] => {
+                p += 1;
+                i += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    i = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+// `%h` is replaced by the original host name and `%%` by a literal `%`
+fn expand_tokens(value: &str, alias: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('h') => result.push_str(alias),
+            Some('%') => result.push('%'),
+            Some(other) => {
+                result.push('%');
+                result.push(other);
+            }
+            None => result.push('%'),
+        }
+    }
+    result
+}
+
+// relative paths are resolved against the directory of the top-level config
+// file (`~/.ssh` by default) and wildcards in the file name are expanded
+fn expand_include(dir: Option<&Path>, arg: &str) -> Result<Vec<PathBuf>, ConfigError> {
+    let home = env::var_os("HOME").map(PathBuf::from);
+
+    let path = if let Some(rest) = arg.strip_prefix("~/") {
+        match home {
+            Some(home) => home.join(rest),
+            None => return Ok(Vec::new()),
+        }
+    } else if Path::new(arg).is_absolute() {
+        PathBuf::from(arg)
+    } else {
+        match (dir, home) {
+            (Some(dir), _) => dir.join(arg),
+            (None, Some(home)) => home.join(".ssh").join(arg),
+            (None, None) => return Ok(Vec::new()),
+        }
+    };
+
+    let file_name = path
+        .file_name()
+        .and_then(|file_name| file_name.to_str())
+        .unwrap_or_default();
+    if !file_name.contains(|c| c == '*' || c == '?') {
+        return Ok(if path.is_file() {
+            vec![path]
+        } else {
+            Vec::new()
+        });
+    }
+
+    let parent = match path.parent() {
+        Some(parent) => parent,
+        None => return Ok(Vec::new()),
+    };
+    let entries = match fs::read_dir(parent) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error.into()),
+    };
+
+    let mut paths = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let matches = entry
+            .file_name()
+            .to_str()
+            .map_or(false, |name| wildcard_match(file_name, name));
+        if matches && entry.path().is_file() {
+            paths.push(entry.path());
+        }
+    }
+    paths.sort();
+
+    Ok(paths)
+}
@@ -0,0 +1,165 @@
+use crate::config::{ConfigError, GitConfig};
+use crate::{HostedGitInfo, ParseError};
+use std::fs;
+use std::path::Path;
+
+/// A `[submodule "<name>"]` section of a `.gitmodules` file.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Submodule {
+    name: String,
+    path: Option<String>,
+    branch: Option<String>,
+    url: Option<String>,
+    resolved_url: Option<String>,
+    info: Result<HostedGitInfo, ParseError>,
+}
+
+impl Submodule {
+    /// The name of the submodule.
+    ///
+    /// Example: `[submodule "vendor/foo"]` → `vendor/foo`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The path of the submodule, relative to the superproject's work tree.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// The remote branch that is tracked by the submodule, if configured.
+    pub fn branch(&self) -> Option<&str> {
+        self.branch.as_deref()
+    }
+
+    /// The URL as written in the `.gitmodules` file.
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    /// The URL after relative URLs like `../other.git` have been resolved
+    /// against the superproject remote.
+    ///
+    /// This is `None` if the URL is relative and could not be resolved.
+    pub fn resolved_url(&self) -> Option<&str> {
+        self.resolved_url.as_deref()
+    }
+
+    /// The result of passing [Submodule::resolved_url] to [HostedGitInfo::from_url].
+    ///
+    /// Submodules without a (resolvable) URL return [ParseError::UnknownUrl].
+    pub fn info(&self) -> Result<&HostedGitInfo, ParseError> {
+        self.info.as_ref().map_err(|error| *error)
+    }
+}
+
+/// Reads the `.gitmodules` file in the work tree at `path`.
+///
+/// Relative submodule URLs are resolved against `superproject_remote`, which
+/// usually is the URL of the superproject's `origin` remote. Submodules that
+/// are not hosted on a supported provider are still returned, but their
+/// [Submodule::info] is a [ParseError].
+pub fn read_submodules<P: AsRef<Path>>(
+    path: P,
+    superproject_remote: Option<&str>,
+) -> Result<Vec<Submodule>, ConfigError> {
+    let content = fs::read_to_string(path.as_ref().join(".gitmodules"))?;
+    parse_submodules(&content, superproject_remote)
+}
+
+/// Parses the content of a `.gitmodules` file.
+///
+/// See [read_submodules] for details.
+///
+/// ```
+/// use hosted_git_info::{parse_submodules, Provider};
+///
+/// let gitmodules = "[submodule \"foo\"]\n\tpath = vendor/foo\n\turl = ../foo.git\n";
+/// let submodules = parse_submodules(gitmodules, Some("git@github.com:org/main.git")).unwrap();
+/// assert_eq!(submodules[0].resolved_url(), Some("git@github.com:org/foo.git"));
+/// assert_eq!(submodules[0].info().unwrap().provider(), Provider::GitHub);
+/// ```
+pub fn parse_submodules(
+    input: &str,
+    superproject_remote: Option<&str>,
+) -> Result<Vec<Submodule>, ConfigError> {
+    let config = GitConfig::parse(input)?;
+
+    let mut submodules: Vec<Submodule> = Vec::new();
+    for entry in config.entries() {
+        let name = match &entry.subsection {
+            Some(name) if entry.section == "submodule" => name,
+            _ => continue,
+        };
+
+        let index = match submodules.iter().position(|s| &s.name == name) {
+            Some(index) => index,
+            None => {
+                submodules.push(Submodule {
+                    name: name.clone(),
+                    path: None,
+                    branch: None,
+                    url: None,
+                    resolved_url: None,
+                    info: Err(ParseError::UnknownUrl),
+                });
+                submodules.len() - 1
+            }
+        };
+
+        // like in git, the last value wins
+        let submodule = &mut submodules[index];
+        match entry.key.as_str() {
+            "path" => submodule.path = entry.value.clone(),
+            "branch" => submodule.branch = entry.value.clone(),
+            "url" => submodule.url = entry.value.clone(),
+            _ => {}
+        }
+    }
+
+    for submodule in &mut submodules {
+        submodule.resolved_url = submodule
+            .url
+            .as_deref()
+            .and_then(|url| resolve_relative_url(url, superproject_remote));
+
+        if let Some(url) = &submodule.resolved_url {
+            submodule.info = HostedGitInfo::from_url(url);
+        }
+    }
+
+    Ok(submodules)
+}
+
+// see `relative_url()` in git's `remote.c`
+fn resolve_relative_url(url: &str, remote: Option<&str>) -> Option<String> {
+    if !url.starts_with("./") && !url.starts_with("../") {
+        return Some(url.to_string());
+    }
+
+    let mut remote = remote?.trim_end_matches('/').to_string();
+    let mut url = url;
+    let mut colon_separated = false;
+    loop {
+        if let Some(rest) = url.strip_prefix("../") {
+            url = rest;
+
+            // chop off the last path component of the remote URL
+            if let Some(last_slash) = remote.rfind('/') {
+                remote.truncate(last_slash);
+            } else if let Some(last_colon) = remote.rfind(':') {
+                remote.truncate(last_colon);
+                colon_separated = true;
+            } else {
+                return None;
+            }
+        } else if let Some(rest) = url.strip_prefix("./") {
+            url = rest;
+        } else {
+            break;
+        }
+    }
+
+    let separator = if colon_separated { ':' } else { '/' };
+    Some(format!("{}{}{}", remote, separator, url))
+}
@@ -0,0 +1,247 @@
+use crate::report::Layout;
+use crate::{redact_credentials, HostedGitInfo, Provider};
+use std::borrow::Cow;
+use std::cmp;
+use std::fmt;
+
+// the maximum number of edits for a candidate to be suggested
+const MAX_DISTANCE: usize = 2;
+
+/// A corrected version of an input that failed to parse, see [suggest].
+#[derive(Eq, PartialEq, Clone)]
+pub struct Suggestion {
+    input: String,
+    info: HostedGitInfo,
+    distance: usize,
+}
+
+impl Suggestion {
+    /// The corrected input, e.g. `https://github.com/a/b` for
+    /// `https://gitub.com/a/b`.
+    ///
+    /// Credentials are redacted like in [redact_credentials]. The
+    /// [info](Suggestion::info) still contains them.
+    pub fn input(&self) -> Cow<'_, str> {
+        redact_credentials(&self.input)
+    }
+
+    /// The parsed corrected input.
+    pub fn info(&self) -> &HostedGitInfo {
+        &self.info
+    }
+
+    /// The number of edits between the original and the corrected part of
+    /// the input. Lower is better.
+    pub fn distance(&self) -> usize {
+        self.distance
+    }
+
+    /// Converts this into the parsed corrected input.
+    pub fn into_info(self) -> HostedGitInfo {
+        self.info
+    }
+}
+
+impl fmt::Debug for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the input may contain credentials, so they are redacted like `Auth`
+        f.debug_struct("Suggestion")
+            .field("input", &self.input())
+            .field("info", &self.info)
+            .field("distance", &self.distance)
+            .finish()
+    }
+}
+
+/// Suggests corrections for an input that fails to parse because of a typo
+/// in the provider shortcut, the host or the scheme.
+///
+/// The suggestions are sorted by their [distance](Suggestion::distance), and
+/// only include inputs that can be parsed. Inputs that already parse have no
+/// suggestions.
+///
+/// ```
+/// use hosted_git_info::suggest;
+///
+/// let suggestions = suggest("githb:user/repo");
+/// assert_eq!(suggestions[0].input(), "github:user/repo");
+///
+/// let suggestions = suggest("https://gitub.com/a/b");
+/// assert_eq!(suggestions[0].input(), "https://github.com/a/b");
+/// assert_eq!(suggestions[0].info().project(), "b");
+/// ```
+pub fn suggest(giturl: &str) -> Vec<Suggestion> {
+    if HostedGitInfo::from_url(giturl).is_ok() {
+        return Vec::new();
+    }
+
+    let layout = Layout::new(giturl);
+    let mut candidates = Vec::new();
+
+    if let Some(host) = &layout.host {
+        let name = giturl[host.clone()].to_lowercase();
+        let (prefix, simplified) = match name.strip_prefix("www.") {
+            Some(simplified) => ("www.", simplified),
+            None => ("", name.as_str()),
+        };
+
+        for provider in Provider::all() {
+            let distance = domain_distance(simplified, provider.domain());
+            if distance > 0 && distance <= MAX_DISTANCE {
+                let replacement = format!("{}{}", prefix, provider.domain());
+                candidates.push((replace(giturl, host, &replacement), distance));
+            }
+        }
+
+        let provider = Provider::from_domain(simplified)
+            .or_else(|| closest_domain(simplified).map(|(p, _)| p));
+        if let (Some(provider), Some(scheme)) = (provider, &layout.scheme) {
+            let name = giturl[scheme.clone()].to_lowercase();
+            let supported_schemes = provider.supported_schemes();
+            for supported in supported_schemes
+                .iter()
+                .filter(|_| !supported_schemes.contains(&name.as_str()))
+            {
+                let distance = edit_distance(&name, supported);
+                if distance > 0 && distance <= MAX_DISTANCE {
+                    candidates.push((replace(giturl, scheme, supported), distance));
+                }
+            }
+        }
+    } else if let Some(scheme) = &layout.scheme {
+        let name = giturl[scheme.clone()].to_lowercase();
+        for provider in Provider::all() {
+            let distance = edit_distance(&name, provider.shortcut());
+            if distance > 0 && distance <= MAX_DISTANCE {
+                candidates.push((replace(giturl, scheme, provider.shortcut()), distance));
+            }
+        }
+    }
+
+    // combine host and scheme corrections, e.g. for `htps://gitub.com/a/b`
+    let combined: Vec<_> = candidates
+        .iter()
+        .flat_map(|(input, distance)| {
+            suggest_once(input).map(move |(input, more)| (input, distance + more))
+        })
+        .collect();
+    candidates.extend(combined);
+
+    candidates.sort_by_key(|(_, distance)| *distance);
+
+    let mut suggestions: Vec<Suggestion> = Vec::new();
+    for (input, distance) in candidates {
+        if suggestions.iter().any(|s| s.input == input) {
+            continue;
+        }
+        if let Ok(info) = HostedGitInfo::from_url(&input) {
+            suggestions.push(Suggestion {
+                input,
+                info,
+                distance,
+            });
+        }
+    }
+    suggestions
+}
+
+// the best scheme correction for an input whose host is already known
+fn suggest_once(giturl: &str) -> Option<(String, usize)> {
+    let layout = Layout::new(giturl);
+    let host = giturl[layout.host?].to_lowercase();
+    let provider = Provider::from_domain(host.strip_prefix("www.").unwrap_or(&host))?;
+    let scheme = layout.scheme?;
+    let name = giturl[scheme.clone()].to_lowercase();
+    if provider.supported_schemes().contains(&name.as_str()) {
+        return None;
+    }
+
+    provider
+        .supported_schemes()
+        .iter()
+        .map(|supported| (*supported, edit_distance(&name, supported)))
+        .filter(|(_, distance)| *distance > 0 && *distance <= MAX_DISTANCE)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(supported, distance)| (replace(giturl, &scheme, supported), distance))
+}
+
+/// The provider with the domain that is closest to the (unknown) host.
+pub(crate) fn closest_domain(host: &str) -> Option<(Provider, usize)> {
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    closest(|provider| domain_distance(host, provider.domain()))
+}
+
+/// The provider with the shortcut that is closest to the (unknown) shortcut.
+pub(crate) fn closest_shortcut(shortcut: &str) -> Option<(Provider, usize)> {
+    closest(|provider| edit_distance(shortcut, provider.shortcut()))
+}
+
+fn closest<F: Fn(Provider) -> usize>(distance: F) -> Option<(Provider, usize)> {
+    Provider::all()
+        .iter()
+        .map(|provider| (*provider, distance(*provider)))
+        .filter(|(_, distance)| *distance > 0 && *distance <= MAX_DISTANCE)
+        .min_by_key(|(_, distance)| *distance)
+}
+
+// compares the whole domain, and the name without the top-level domain with
+// one edit for a different top-level domain, so that `gitlab.org` is close
+// to `gitlab.com`
+fn domain_distance(host: &str, domain: &str) -> usize {
+    let full = edit_distance(host, domain);
+
+    let (host_name, host_tld) = split_tld(host);
+    let (domain_name, domain_tld) = split_tld(domain);
+    let by_name = edit_distance(host_name, domain_name) + (host_tld != domain_tld) as usize;
+
+    cmp::min(full, by_name)
+}
+
+fn split_tld(host: &str) -> (&str, &str) {
+    match host.rfind('.') {
+        Some(dot) => (&host[..dot], &host[dot + 1..]),
+        None => (host, ""),
+    }
+}
+
+fn replace(input: &str, range: &std::ops::Range<usize>, replacement: &str) -> String {
+    format!(
+        "{}{}{}",
+        &input[..range.start],
+        replacement,
+        &input[range.end..]
+    )
+}
+
+/// The number of insertions, deletions, substitutions and transpositions of
+/// adjacent characters that turn `a` into `b`.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // `rows[i][j]` is the distance between the first `i` characters of `a`
+    // and the first `j` characters of `b`
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = cmp::min(
+                cmp::min(rows[i - 1][j] + 1, rows[i][j - 1] + 1),
+                rows[i - 1][j - 1] + cost,
+            );
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = cmp::min(distance, rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
@@ -0,0 +1,167 @@
+//! JavaScript bindings, mirroring the API of the `hosted-git-info` npm
+//! package, so that the crate can be used as a drop-in replacement for it.
+//!
+//! The option objects of the npm package (`noCommittish`, `noGitPlus`, …)
+//! are not supported.
+//!
+//! Note that recent `wasm-bindgen` releases require Rust 1.81, which is
+//! newer than the minimum supported Rust version of the rest of this crate.
+
+use crate::{DefaultRepresentation, HostedGitInfo};
+use wasm_bindgen::prelude::*;
+
+// const fromUrl = (giturl, opts) => { ... }
+/// Returns `undefined` if the URL can not be parsed.
+#[wasm_bindgen(js_name = fromUrl)]
+pub fn from_url(giturl: &str) -> Option<GitHost> {
+    HostedGitInfo::from_url(giturl).ok().map(GitHost)
+}
+
+/// The equivalent of the `GitHost` class in `git-host.js`.
+#[wasm_bindgen]
+pub struct GitHost(HostedGitInfo);
+
+#[wasm_bindgen]
+impl GitHost {
+    #[wasm_bindgen(getter, js_name = type)]
+    pub fn provider(&self) -> String {
+        self.0.provider().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn domain(&self) -> String {
+        self.0.provider().domain().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn user(&self) -> Option<String> {
+        self.0.user().map(str::to_string)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn project(&self) -> String {
+        self.0.project().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn committish(&self) -> Option<String> {
+        self.0.committish().map(str::to_string)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn directory(&self) -> Option<String> {
+        self.0.directory().map(str::to_string)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn auth(&self) -> Option<String> {
+        self.0.auth().map(str::to_string)
+    }
+
+    #[wasm_bindgen(getter, js_name = default)]
+    pub fn default_representation(&self) -> String {
+        self.get_default_representation()
+    }
+
+    // getDefaultRepresentation () {
+    //   return this.default
+    // }
+    #[wasm_bindgen(js_name = getDefaultRepresentation)]
+    pub fn get_default_representation(&self) -> String {
+        let name = match self.0.default_representation() {
+            DefaultRepresentation::Shortcut => "shortcut",
+            DefaultRepresentation::Git => "git",
+            DefaultRepresentation::Https => "https",
+            DefaultRepresentation::Ssh => "sshurl",
+            // like `protocol.slice(0, -1)` for the protocols that are not
+            // in the protocol map, e.g. `http`. Values that were not parsed
+            // have no scheme.
+            DefaultRepresentation::Other => self.0.scheme().unwrap_or("other"),
+        };
+        name.to_string()
+    }
+
+    // hash () {
+    //   return this.committish ? `#${this.committish}` : ''
+    // }
+    pub fn hash(&self) -> String {
+        self.0
+            .committish()
+            .map(|committish| format!("#{}", committish))
+            .unwrap_or_default()
+    }
+
+    pub fn ssh(&self) -> String {
+        self.0.ssh()
+    }
+
+    pub fn sshurl(&self) -> String {
+        self.0.sshurl()
+    }
+
+    // browse (path, fragment, opts) {
+    //   // not a string, treat path as opts
+    //   if (typeof path !== 'string') {
+    //     return this._fill(this.browsetemplate, path)
+    //   }
+    //   ...
+    // }
+    pub fn browse(&self, path: Option<String>, fragment: Option<String>) -> String {
+        match path {
+            Some(path) => self.0.browse_file(&path, fragment.as_deref()),
+            None => self.0.browse(),
+        }
+    }
+
+    pub fn docs(&self) -> String {
+        self.0.docs()
+    }
+
+    pub fn bugs(&self) -> String {
+        self.0.bugs()
+    }
+
+    pub fn https(&self) -> String {
+        self.0.https()
+    }
+
+    pub fn git(&self) -> Option<String> {
+        self.0.git()
+    }
+
+    pub fn shortcut(&self) -> String {
+        self.0.shortcut()
+    }
+
+    pub fn path(&self) -> String {
+        self.0.path()
+    }
+
+    pub fn tarball(&self) -> String {
+        self.0.tarball()
+    }
+
+    pub fn file(&self, path: &str) -> String {
+        self.0.file(path)
+    }
+
+    // toString (opts) {
+    //   if (this.default && typeof this[this.default] === 'function') {
+    //     return this[this.default](opts)
+    //   }
+    //
+    //   return this.sshurl(opts)
+    // }
+    //
+    // unlike `Display`, this includes the auth, like the npm package does
+    #[wasm_bindgen(js_name = toString)]
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        match self.0.default_representation() {
+            DefaultRepresentation::Shortcut => self.0.shortcut(),
+            DefaultRepresentation::Git => self.0.git().unwrap_or_else(|| self.0.sshurl()),
+            DefaultRepresentation::Https => self.0.https(),
+            DefaultRepresentation::Ssh | DefaultRepresentation::Other => self.0.sshurl(),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use hosted_git_info::{parse_batch, HostedGitInfo, ParseErrorKind, Provider};
+
+const INPUTS: [&str; 7] = [
+    "foo/bar",
+    "https://github.com/foo",
+    "gitlab:foo/bar",
+    "https://example.com/foo/bar",
+    "github:%ff/bar",
+    "https://github.com/foo/bar/baz/qux",
+    "git+ssh://git@bitbucket.org/foo/bar.git",
+];
+
+#[test]
+fn results_in_order() {
+    let batch = parse_batch(INPUTS.iter());
+    assert_eq!(batch.results().len(), INPUTS.len());
+    for (input, result) in INPUTS.iter().zip(batch.results()) {
+        assert_eq!(result, &HostedGitInfo::from_url(input));
+    }
+}
+
+#[test]
+fn report() {
+    let batch = parse_batch(INPUTS.iter().map(|input| input.to_string()));
+    let report = batch.report();
+    assert_eq!(report.total(), 7);
+    assert_eq!(report.succeeded(), 3);
+    assert_eq!(report.failed(), 4);
+
+    let groups: Vec<_> = report
+        .groups()
+        .iter()
+        .map(|group| (group.kind(), group.provider(), group.indices().to_vec()))
+        .collect();
+
+    assert_eq!(
+        groups,
+        vec![
+            (
+                ParseErrorKind::InvalidUriEncoding,
+                Some(Provider::GitHub),
+                vec![4]
+            ),
+            (ParseErrorKind::UnknownUrl, None, vec![3]),
+            (
+                ParseErrorKind::UnknownUrl,
+                Some(Provider::GitHub),
+                vec![1, 5]
+            ),
+        ]
+    );
+
+    assert_eq!(
+        report.to_string(),
+        "4 of 7 inputs failed to parse\n  InvalidUriEncoding (github): 1\n  UnknownUrl (unknown provider): 1\n  UnknownUrl (github): 2"
+    );
+}
+
+#[test]
+fn empty() {
+    let batch = parse_batch(Vec::<String>::new());
+    assert!(batch.results().is_empty());
+    assert_eq!(batch.report().total(), 0);
+    assert!(batch.report().groups().is_empty());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_matches_sequential() {
+    use hosted_git_info::parse_batch_parallel;
+
+    let inputs: Vec<_> = INPUTS.iter().cycle().take(1000).collect();
+    let parallel = parse_batch_parallel(&inputs);
+    assert_eq!(parallel, parse_batch(&inputs));
+    assert!(parallel.results()[0].is_ok());
+}
@@ -356,3 +356,38 @@ fn invalid() {
     // missing project
     check_err("https://bitbucket.org/foo");
 }
+
+#[test]
+fn string_methods() {
+    let parsed = HostedGitInfo::from_url("bitbucket:foo/bar").unwrap();
+    assert_eq!(parsed.ssh(), "git@bitbucket.org:foo/bar.git");
+    assert_eq!(parsed.sshurl(), "git+ssh://git@bitbucket.org/foo/bar.git");
+    assert_eq!(parsed.browse(), "https://bitbucket.org/foo/bar");
+    assert_eq!(
+        parsed.browse_file("/lib/index.js", Some("L100")),
+        "https://bitbucket.org/foo/bar/src/master/lib/index.js#l100"
+    );
+    assert_eq!(parsed.docs(), "https://bitbucket.org/foo/bar#readme");
+    assert_eq!(parsed.https(), "git+https://bitbucket.org/foo/bar.git");
+    assert_eq!(parsed.shortcut(), "bitbucket:foo/bar");
+    assert_eq!(parsed.path(), "foo/bar");
+    assert_eq!(
+        parsed.tarball(),
+        "https://bitbucket.org/foo/bar/get/master.tar.gz"
+    );
+    assert_eq!(
+        parsed.file("/lib/index.js"),
+        "https://bitbucket.org/foo/bar/raw/master/lib/index.js"
+    );
+    assert_none!(parsed.git());
+    assert_eq!(parsed.bugs(), "https://bitbucket.org/foo/bar/issues");
+}
+
+#[test]
+fn release_and_commit_methods() {
+    let parsed = HostedGitInfo::from_url("bitbucket:foo/bar#v1.0.0").unwrap();
+    assert_none!(parsed.release_page());
+    assert_none!(parsed.release_asset("bar.tar.gz"));
+    assert_none!(parsed.commit_page());
+    assert_none!(parsed.compare("v1.0.0", "main"));
+}
@@ -0,0 +1,96 @@
+use claim::*;
+use hosted_git_info::{CachedParser, HostedGitInfo, ParseError};
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+fn caches_results_and_errors() {
+    let parser = CachedParser::new();
+    assert_eq!(parser.stats().capacity(), 1000);
+
+    let expected = HostedGitInfo::from_url("github:foo/bar").unwrap();
+    assert_ok_eq!(parser.parse("github:foo/bar"), expected.clone());
+    assert_ok_eq!(parser.parse("github:foo/bar"), expected);
+
+    let invalid = "https://example.com/foo/bar";
+    assert_eq!(parser.parse(invalid), Err(ParseError::UnknownUrl));
+    assert_eq!(parser.parse(invalid), Err(ParseError::UnknownUrl));
+
+    let stats = parser.stats();
+    assert_eq!(stats.hits(), 2);
+    assert_eq!(stats.misses(), 2);
+    assert_eq!(stats.len(), 2);
+
+    parser.clear();
+    let stats = parser.stats();
+    assert_eq!(stats.hits(), 0);
+    assert!(stats.is_empty());
+}
+
+#[test]
+fn evicts_least_recently_used() {
+    let parser = CachedParser::with_capacity(2);
+    assert_ok!(parser.parse("foo/a"));
+    assert_ok!(parser.parse("foo/b"));
+    // `foo/a` is now the most recently used entry
+    assert_ok!(parser.parse("foo/a"));
+    // so `foo/b` is evicted
+    assert_ok!(parser.parse("foo/c"));
+    assert_eq!(parser.stats().len(), 2);
+
+    assert_ok!(parser.parse("foo/a"));
+    assert_ok!(parser.parse("foo/c"));
+    assert_eq!(parser.stats().hits(), 3);
+
+    assert_ok!(parser.parse("foo/b"));
+    assert_eq!(parser.stats().misses(), 4);
+}
+
+#[test]
+fn zero_capacity_disables_caching() {
+    let parser = CachedParser::with_capacity(0);
+    assert_ok!(parser.parse("foo/bar"));
+    assert_ok!(parser.parse("foo/bar"));
+
+    let stats = parser.stats();
+    assert_eq!(stats.hits(), 0);
+    assert_eq!(stats.misses(), 2);
+    assert!(stats.is_empty());
+}
+
+#[test]
+fn shared_between_threads() {
+    let parser = Arc::new(CachedParser::with_capacity(10));
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let parser = Arc::clone(&parser);
+            thread::spawn(move || {
+                for i in 0..100 {
+                    assert_ok!(parser.parse(&format!("foo/bar-{}", i % 20)));
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let stats = parser.stats();
+    assert_eq!(stats.hits() + stats.misses(), 400);
+    assert_eq!(stats.len(), 10);
+}
+
+#[test]
+fn uses_options() {
+    use hosted_git_info::{Leniency, ParseOptions};
+
+    let parser = CachedParser::new().with_options(ParseOptions::strict());
+    assert_eq!(parser.options(), &ParseOptions::strict());
+    assert_eq!(
+        parser.parse("foo/bar"),
+        Err(ParseError::RequiresLeniency(Leniency::GitHubShorthand))
+    );
+    assert_ok!(parser.parse("github:foo/bar"));
+}
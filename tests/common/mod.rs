@@ -0,0 +1,43 @@
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// An empty directory for the fixtures of a single test, which is unique per
+/// test binary and test name, and is removed again when it is dropped.
+pub struct TmpDir {
+    path: PathBuf,
+}
+
+impl Deref for TmpDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for TmpDir {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TmpDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+        // the directory of the test binary is only removed by the last test
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::remove_dir(parent);
+        }
+    }
+}
+
+/// Creates an empty directory for the fixtures of a single test.
+pub fn tmp_dir(name: &str) -> TmpDir {
+    let path = std::env::temp_dir()
+        .join(format!("hosted-git-info-{}", std::process::id()))
+        .join(name);
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    TmpDir { path }
+}
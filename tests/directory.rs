@@ -0,0 +1,174 @@
+use claim::*;
+use hosted_git_info::{
+    BuildError, HostedGitInfo, HostedGitInfoBuilder, HostedGitInfoRef, Provider,
+};
+
+#[track_caller]
+fn parse(input: &str) -> HostedGitInfo {
+    HostedGitInfo::from_url(input).unwrap()
+}
+
+#[test]
+fn tree_urls() {
+    let info = parse("https://github.com/a/b/tree/main/packages/x");
+    assert_eq!(info.committish(), Some("main"));
+    assert_eq!(info.directory(), Some("packages/x"));
+
+    let info = parse("https://github.com/a/b/tree/main/");
+    assert_eq!(info.committish(), Some("main"));
+    assert_none!(info.directory());
+
+    let info = parse("https://bitbucket.org/a/b/src/v1/packages/my%20x/");
+    assert_eq!(info.committish(), Some("v1"));
+    assert_eq!(info.directory(), Some("packages/my x"));
+
+    let info = parse("https://gitlab.com/group/sub/b/-/tree/main/packages/x");
+    assert_eq!(info.user(), Some("group/sub"));
+    assert_eq!(info.committish(), Some("main"));
+    assert_eq!(info.directory(), Some("packages/x"));
+}
+
+#[test]
+fn tree_urls_with_slash_branches() {
+    // the first segment is the committish, the rest is the directory
+    let inputs = [
+        "https://github.com/a/b/tree/feature/foo/pkg",
+        "https://bitbucket.org/a/b/src/feature/foo/pkg",
+        "https://gitlab.com/a/sub/b/-/tree/feature/foo/pkg",
+    ];
+    for input in &inputs {
+        let info = parse(input);
+        assert_eq!(info.committish(), Some("feature"), "{}", input);
+        assert_eq!(info.directory(), Some("foo/pkg"), "{}", input);
+    }
+
+    // encoded slashes are part of the committish
+    let inputs = [
+        "https://github.com/a/b/tree/feature%2Ffoo/pkg",
+        "https://bitbucket.org/a/b/src/feature%2Ffoo/pkg",
+        "https://gitlab.com/a/sub/b/-/tree/feature%2Ffoo/pkg",
+    ];
+    for input in &inputs {
+        let info = parse(input);
+        assert_eq!(info.committish(), Some("feature/foo"), "{}", input);
+        assert_eq!(info.directory(), Some("pkg"), "{}", input);
+        assert_eq!(parse(&info.browse()), info, "{}", input);
+    }
+}
+
+#[test]
+fn npm_fragments() {
+    let info = parse("github:a/b#main::path:packages/x");
+    assert_eq!(info.committish(), Some("main"));
+    assert_eq!(info.directory(), Some("packages/x"));
+
+    let info = parse("git+https://github.com/a/b.git#path:/packages/x/");
+    assert_none!(info.committish());
+    assert_eq!(info.directory(), Some("packages/x"));
+
+    let info = parse("git+ssh://git@gitlab.com/a/b.git#semver:^1.0.0::path:packages/x");
+    assert_eq!(info.committish(), Some("semver:^1.0.0"));
+    assert_eq!(info.directory(), Some("packages/x"));
+
+    // fragments without a `path:` attribute are unchanged
+    let info = parse("github:a/b#feature::x");
+    assert_eq!(info.committish(), Some("feature::x"));
+    assert_none!(info.directory());
+}
+
+#[test]
+fn rendering() {
+    let info = parse("https://github.com/a/b/tree/main/packages/x");
+    assert_eq!(info.shortcut(), "github:a/b#main::path:packages/x");
+    assert_eq!(
+        info.sshurl(),
+        "git+ssh://git@github.com/a/b.git#main::path:packages/x"
+    );
+    assert_eq!(info.browse(), "https://github.com/a/b/tree/main/packages/x");
+    assert_eq!(
+        info.docs(),
+        "https://github.com/a/b/tree/main/packages/x#readme"
+    );
+    assert_eq!(
+        info.browse_file("/src/index.js", None),
+        "https://github.com/a/b/tree/main/packages/x/src/index.js"
+    );
+    assert_eq!(
+        info.file("package.json"),
+        "https://raw.githubusercontent.com/a/b/main/packages/x/package.json"
+    );
+    // archives always contain the whole repository
+    assert_eq!(
+        info.tarball(),
+        "https://codeload.github.com/a/b/tar.gz/main"
+    );
+
+    let info = parse("gitlab:group/sub/b#path:packages/x");
+    assert_eq!(
+        info.browse(),
+        "https://gitlab.com/group/sub/b/-/tree/master/packages/x"
+    );
+    assert_eq!(
+        info.file("package.json"),
+        "https://gitlab.com/group/sub/b/-/raw/master/packages/x/package.json"
+    );
+
+    let info = parse("bitbucket:a/b#v1::path:my dir");
+    assert_eq!(info.browse(), "https://bitbucket.org/a/b/src/v1/my%20dir");
+}
+
+#[test]
+fn round_trip() {
+    let inputs = [
+        "https://github.com/a/b/tree/main/packages/x",
+        "gitlab:group/sub/b#v1::path:packages/x",
+        "https://bitbucket.org/a/b/src/v1/packages/x",
+    ];
+
+    for input in &inputs {
+        let info = parse(input);
+        assert_eq!(parse(&info.to_string()), info, "{}", input);
+        assert_eq!(
+            parse(&info.browse()).directory(),
+            info.directory(),
+            "{}",
+            input
+        );
+        assert_ok_eq!(
+            HostedGitInfoRef::parse(input).map(HostedGitInfoRef::into_owned),
+            info.clone()
+        );
+    }
+}
+
+#[test]
+fn modifiers_and_builder() {
+    let info = parse("github:a/b#main");
+    let with = info.with_directory("/packages/x/");
+    assert_eq!(with.directory(), Some("packages/x"));
+    assert_ne!(with, info);
+    assert_eq!(with.without_directory(), info);
+    assert_none!(info.with_directory("").directory());
+
+    let built = HostedGitInfoBuilder::new()
+        .provider(Provider::GitHub)
+        .user("a")
+        .project("b")
+        .committish("main")
+        .directory("packages/x/")
+        .repr(hosted_git_info::DefaultRepresentation::Shortcut)
+        .build()
+        .unwrap();
+    assert_eq!(built, with);
+
+    assert_eq!(
+        HostedGitInfoBuilder::new()
+            .provider(Provider::GitHub)
+            .user("a")
+            .project("b")
+            .directory("/")
+            .repr(hosted_git_info::DefaultRepresentation::Shortcut)
+            .build(),
+        Err(BuildError::InvalidDirectory("/".into()))
+    );
+}
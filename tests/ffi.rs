@@ -0,0 +1,90 @@
+#![cfg(all(feature = "ffi", unix))]
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const HEADER: &str = "hosted_git_info.h";
+
+fn manifest_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+// target/debug/deps/ffi-<hash> → target/debug
+fn target_dir() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+// the library is not built as a cdylib by default, so it is built here, in a
+// separate target directory, because the one of `cargo test` is locked while
+// the tests are running
+fn build_cdylib() -> PathBuf {
+    let cdylib_target_dir = target_dir().join("ffi-cdylib");
+
+    let status = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir())
+        .args(&[
+            "rustc",
+            "--lib",
+            "--features",
+            "ffi",
+            "--crate-type",
+            "cdylib",
+        ])
+        .arg("--target-dir")
+        .arg(&cdylib_target_dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "Failed to build the cdylib");
+
+    cdylib_target_dir.join("debug")
+}
+
+#[test]
+fn header_is_up_to_date() {
+    let generated = fs::read_to_string(Path::new(env!("OUT_DIR")).join(HEADER)).unwrap();
+    let committed = fs::read_to_string(manifest_dir().join("include").join(HEADER)).unwrap();
+    assert!(
+        generated == committed,
+        "include/{} is outdated, copy it from {}",
+        HEADER,
+        env!("OUT_DIR")
+    );
+}
+
+#[test]
+fn c_test() {
+    let library_dir = build_cdylib();
+    let binary = target_dir().join("ffi-c-test");
+
+    let status = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(manifest_dir().join("tests").join("ffi").join("test.c"))
+        .arg("-I")
+        .arg(env!("OUT_DIR"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .arg("-lhosted_git_info")
+        .arg("-o")
+        .arg(&binary)
+        .status()
+        .unwrap();
+    assert!(status.success(), "Failed to compile tests/ffi/test.c");
+
+    // `cargo test` points the library search path to `target/debug/deps`,
+    // which may contain an older build of the library without the `ffi`
+    // feature, and would take precedence over the rpath
+    let output = Command::new(&binary)
+        .env_remove("LD_LIBRARY_PATH")
+        .env_remove("DYLD_LIBRARY_PATH")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
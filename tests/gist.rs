@@ -968,3 +968,234 @@ fn invalid() {
     // missing both user and project
     check_err("https://gist.github.com/");
 }
+
+#[test]
+fn string_methods() {
+    let parsed = HostedGitInfo::from_url("git+https://gist.github.com/feedbeef").unwrap();
+    assert_eq!(parsed.ssh(), "git@gist.github.com:feedbeef.git");
+    assert_eq!(
+        parsed.sshurl(),
+        "git+ssh://git@gist.github.com/feedbeef.git"
+    );
+    assert_eq!(parsed.browse(), "https://gist.github.com/feedbeef");
+    assert_eq!(
+        parsed.browse_file("/lib/index.js", None),
+        "https://gist.github.com/feedbeef#file-libindex-js"
+    );
+    assert_eq!(parsed.docs(), "https://gist.github.com/feedbeef");
+    assert_eq!(parsed.https(), "git+https://gist.github.com/feedbeef.git");
+    assert_eq!(parsed.shortcut(), "gist:feedbeef");
+    assert_eq!(parsed.path(), "feedbeef");
+    assert_eq!(
+        parsed.tarball(),
+        "https://codeload.github.com/gist/feedbeef/tar.gz/master"
+    );
+    assert_some_eq!(parsed.git(), "git://gist.github.com/feedbeef.git");
+    assert_eq!(parsed.bugs(), "https://gist.github.com/feedbeef");
+
+    let parsed = HostedGitInfo::from_url("gist:foo/feedbeef#branch").unwrap();
+    assert_eq!(parsed.browse(), "https://gist.github.com/feedbeef/branch");
+    assert_eq!(
+        parsed.file("/lib/index.js"),
+        "https://gist.githubusercontent.com/foo/feedbeef/raw/branch/lib/index.js"
+    );
+}
+
+const REV: &str = "0123456789abcdef0123456789abcdef01234567";
+
+#[test]
+fn raw_urls() {
+    let input = format!(
+        "https://gist.githubusercontent.com/foo/feedbeef/raw/{}/file.txt",
+        REV
+    );
+    check(
+        &input,
+        d().repr(Https)
+            .user("foo")
+            .committish(REV)
+            .filename("file.txt"),
+    );
+    check(
+        "https://gist.githubusercontent.com/foo/feedbeef/raw/my%20file.txt",
+        d().repr(Https).user("foo").filename("my file.txt"),
+    );
+    check(
+        "https://gist.github.com/foo/feedbeef/raw/branch/file.txt",
+        d().repr(Https)
+            .user("foo")
+            .committish("branch")
+            .filename("file.txt"),
+    );
+
+    check_err("https://gist.githubusercontent.com/foo/feedbeef");
+    check_err("https://gist.githubusercontent.com/foo/feedbeef/raw/");
+    check_err("https://gist.githubusercontent.com/a/b/c/raw/file.txt");
+}
+
+#[test]
+fn revision_urls() {
+    check(
+        &format!("https://gist.github.com/foo/feedbeef/{}", REV),
+        d().repr(Https).user("foo").committish(REV),
+    );
+    // other pages of a gist are not revisions
+    check(
+        "https://gist.github.com/foo/feedbeef/revisions",
+        d().repr(Https).user("foo"),
+    );
+}
+
+#[test]
+fn embed_urls() {
+    check("https://gist.github.com/feedbeef.js", d().repr(Https));
+    check(
+        "https://gist.github.com/foo/feedbeef.js?file=file.txt",
+        d().repr(Https).user("foo").filename("file.txt"),
+    );
+    check(
+        &format!("https://gist.github.com/foo/feedbeef/{}.js", REV),
+        d().repr(Https).user("foo").committish(REV),
+    );
+}
+
+#[test]
+fn file_anchors() {
+    // the anchors are recognized, but are neither a committish nor the
+    // filename
+    check(
+        "https://gist.github.com/foo/feedbeef#file-file-txt",
+        d().repr(Https).user("foo"),
+    );
+    check(
+        "git+https://gist.github.com/foo/feedbeef.git#file-file-txt",
+        d().repr(Https).user("foo"),
+    );
+    check(
+        "git+ssh://git@gist.github.com/feedbeef.git#file-file-txt",
+        d().repr(Ssh),
+    );
+    check(
+        "git://gist.github.com/feedbeef.git#file-file-txt",
+        d().repr(Git),
+    );
+    check(
+        &format!("https://gist.github.com/foo/feedbeef/{}#file-file-txt", REV),
+        d().repr(Https).user("foo").committish(REV),
+    );
+    // an empty anchor is a committish
+    check(
+        "https://gist.github.com/foo/feedbeef#file-",
+        d().repr(Https).user("foo").committish("file-"),
+    );
+
+    // the original representation keeps the user and the anchor
+    let parsed =
+        HostedGitInfo::from_url("https://gist.github.com/foo/feedbeef#file-a-txt").unwrap();
+    assert_eq!(
+        parsed.to_original_representation(),
+        "https://gist.github.com/foo/feedbeef#file-a-txt"
+    );
+    let input = format!("https://gist.github.com/foo/feedbeef/{}#file-a-txt", REV);
+    let parsed = HostedGitInfo::from_url(&input).unwrap();
+    assert_eq!(parsed.to_original_representation(), input);
+}
+
+#[test]
+fn file_urls() {
+    let input = "https://gist.githubusercontent.com/foo/feedbeef/raw/My%20File.txt";
+    let parsed = HostedGitInfo::from_url(input).unwrap();
+    assert_eq!(parsed.filename(), Some("My File.txt"));
+    assert_eq!(
+        parsed.browse(),
+        "https://gist.github.com/feedbeef#file-my-file-txt"
+    );
+    // the path replaces the anchor of the filename
+    assert_eq!(
+        parsed.browse_file("ab.txt", None),
+        "https://gist.github.com/feedbeef#file-ab-txt"
+    );
+    assert_eq!(
+        parsed.raw_file().unwrap(),
+        "https://gist.githubusercontent.com/foo/feedbeef/raw/My%20File.txt"
+    );
+    assert_eq!(
+        parsed.embed().unwrap(),
+        "https://gist.github.com/foo/feedbeef.js?file=My%20File.txt"
+    );
+
+    // the anchors that are generated by `browse()` are parsed again, but
+    // without the filename
+    let parsed = HostedGitInfo::from_url(&parsed.browse()).unwrap();
+    assert_none!(parsed.filename());
+    assert_eq!(parsed.browse(), "https://gist.github.com/feedbeef");
+    assert_eq!(
+        parsed.browse_file("ab.txt", None),
+        "https://gist.github.com/feedbeef#file-ab-txt"
+    );
+    assert_none!(parsed.raw_file());
+    assert_eq!(
+        parsed.embed().unwrap(),
+        "https://gist.github.com/feedbeef.js"
+    );
+}
+
+#[test]
+fn raw_segment_position() {
+    // a user that is named `raw`
+    check(
+        "https://gist.github.com/raw/feedbeef",
+        d().repr(Https).user("raw"),
+    );
+    // other pages of a gist are not raw files
+    check(
+        "https://gist.github.com/foo/feedbeef/revisions/raw/file.txt",
+        d().repr(Https).user("foo"),
+    );
+}
+
+#[test]
+fn gist_generators() {
+    let input = format!(
+        "https://gist.githubusercontent.com/foo/feedbeef/raw/{}/file.txt",
+        REV
+    );
+    let parsed = HostedGitInfo::from_url(&input).unwrap();
+    assert_eq!(parsed.filename(), Some("file.txt"));
+    assert_eq!(
+        parsed.embed().unwrap(),
+        format!(
+            "https://gist.github.com/foo/feedbeef/{}.js?file=file.txt",
+            REV
+        )
+    );
+    assert_eq!(parsed.raw_file().unwrap(), input);
+    assert_eq!(
+        parsed.download_zip().unwrap(),
+        format!("https://gist.github.com/foo/feedbeef/archive/{}.zip", REV)
+    );
+    assert_eq!(
+        parsed.browse(),
+        format!("https://gist.github.com/feedbeef/{}#file-file-txt", REV)
+    );
+    assert_eq!(
+        HostedGitInfo::from_url(&parsed.embed().unwrap()).unwrap(),
+        parsed
+    );
+
+    let parsed = HostedGitInfo::from_url("gist:feedbeef").unwrap();
+    assert_eq!(
+        parsed.embed().unwrap(),
+        "https://gist.github.com/feedbeef.js"
+    );
+    assert_none!(parsed.raw_file());
+    assert_eq!(
+        parsed.download_zip().unwrap(),
+        "https://gist.github.com/feedbeef/archive/master.zip"
+    );
+
+    let parsed = HostedGitInfo::from_url("github:foo/bar").unwrap();
+    assert_none!(parsed.embed());
+    assert_none!(parsed.raw_file());
+    assert_none!(parsed.download_zip());
+}
@@ -639,3 +639,158 @@ fn invalid() {
     check_err("https://gitlab.com/foo/bar/repository/archive.tar.gz");
     check_err("https://gitlab.com/foo/bar/repository/archive.tar.gz?ref=49b393e2ded775f2df36ef2ffcb61b0359c194c9");
 }
+
+#[test]
+fn string_methods() {
+    let parsed = HostedGitInfo::from_url("gitlab:foo/bar").unwrap();
+    assert_eq!(parsed.ssh(), "git@gitlab.com:foo/bar.git");
+    assert_eq!(parsed.sshurl(), "git+ssh://git@gitlab.com/foo/bar.git");
+    assert_eq!(parsed.browse(), "https://gitlab.com/foo/bar");
+    assert_eq!(
+        parsed.browse_file("/lib/index.js", Some("L100")),
+        "https://gitlab.com/foo/bar/tree/master/lib/index.js#l100"
+    );
+    assert_eq!(parsed.docs(), "https://gitlab.com/foo/bar#readme");
+    assert_eq!(parsed.https(), "git+https://gitlab.com/foo/bar.git");
+    assert_eq!(parsed.shortcut(), "gitlab:foo/bar");
+    assert_eq!(parsed.path(), "foo/bar");
+    assert_eq!(
+        parsed.tarball(),
+        "https://gitlab.com/foo/bar/repository/archive.tar.gz?ref=master"
+    );
+    assert_eq!(
+        parsed.file("/lib/index.js"),
+        "https://gitlab.com/foo/bar/raw/master/lib/index.js"
+    );
+    assert_none!(parsed.git());
+    assert_eq!(parsed.bugs(), "https://gitlab.com/foo/bar/issues");
+}
+
+#[test]
+fn empty_path() {
+    assert_err!(HostedGitInfo::from_url(":@gitlab.com:"));
+}
+
+#[test]
+fn dash_tree() {
+    check(
+        "https://gitlab.com/foo/bar/-/tree/branch",
+        d().repr(Https).committish("branch"),
+    );
+    check(
+        "https://gitlab.com/foo/bar/baz/-/tree/branch",
+        s().repr(Https).committish("branch"),
+    );
+    check(
+        "https://gitlab.com/foo/bar/baz/-/tree/v1.0.0/src/lib?ref_type=tags",
+        s().repr(Https).committish("v1.0.0").directory("src/lib"),
+    );
+    check_err("https://gitlab.com/foo/bar/-/tree/");
+    check_err("https://gitlab.com/foo/-/tree/branch");
+    check_err("https://gitlab.com/foo/bar/-/blob/branch/README.md");
+}
+
+#[test]
+fn subgroup_string_methods() {
+    let parsed = HostedGitInfo::from_url("gitlab:foo/bar/baz#v1").unwrap();
+    assert_eq!(parsed.browse(), "https://gitlab.com/foo/bar/baz/-/tree/v1");
+    assert_eq!(
+        parsed.browse_file("/lib/index.js", Some("L100")),
+        "https://gitlab.com/foo/bar/baz/-/tree/v1/lib/index.js#l100"
+    );
+    assert_eq!(
+        parsed.docs(),
+        "https://gitlab.com/foo/bar/baz/-/tree/v1#readme"
+    );
+    assert_eq!(
+        parsed.tarball(),
+        "https://gitlab.com/foo/bar/baz/-/archive/v1/baz-v1.tar.gz"
+    );
+    assert_eq!(
+        parsed.file("/lib/index.js"),
+        "https://gitlab.com/foo/bar/baz/-/raw/v1/lib/index.js"
+    );
+    assert_eq!(
+        HostedGitInfo::from_url(&parsed.browse()).unwrap(),
+        parsed.with_default_representation(Https)
+    );
+}
+
+#[test]
+fn namespace() {
+    let parsed = HostedGitInfo::from_url("https://gitlab.com/a/b/c/project.git").unwrap();
+    let namespace = parsed.namespace().unwrap();
+    assert_eq!(namespace.group(), "a");
+    assert_eq!(namespace.subgroups().collect::<Vec<_>>(), ["b", "c"]);
+    assert_eq!(namespace.depth(), 3);
+    assert!(namespace.has_subgroups());
+    assert_ok!(namespace.validate());
+
+    let parsed = HostedGitInfo::from_url("gitlab:foo/bar").unwrap();
+    let namespace = parsed.namespace().unwrap();
+    assert_eq!(namespace.group(), "foo");
+    assert_eq!(namespace.subgroups().count(), 0);
+    assert_none!(namespace.parent());
+}
+
+#[test]
+fn namespace_validation() {
+    use hosted_git_info::{Namespace, NamespaceError};
+
+    assert_ok!(Namespace::new("my_group/sub.group/v1-2").validate());
+    assert_eq!(
+        Namespace::new("group//sub").validate(),
+        Err(NamespaceError::EmptySegment)
+    );
+    assert_eq!(
+        Namespace::new("group/sub group").validate(),
+        Err(NamespaceError::InvalidCharacter {
+            segment: "sub group".into(),
+            character: ' '
+        })
+    );
+    assert_eq!(
+        Namespace::new(".group").validate(),
+        Err(NamespaceError::InvalidStart(".group".into()))
+    );
+    assert_eq!(
+        Namespace::new("group/sub.git").validate(),
+        Err(NamespaceError::InvalidEnd("sub.git".into()))
+    );
+
+    let deep = vec!["g"; 22].join("/");
+    assert_eq!(
+        Namespace::new(&deep).validate(),
+        Err(NamespaceError::TooDeep(22))
+    );
+    assert_eq!(
+        Namespace::new("group/-sub")
+            .validate()
+            .unwrap_err()
+            .to_string(),
+        "Failed to validate group `-sub` because it starts with a special character"
+    );
+}
+
+#[test]
+fn release_and_commit_methods() {
+    let parsed = HostedGitInfo::from_url("gitlab:group/sub/project#v1.0.0").unwrap();
+    assert_some_eq!(
+        parsed.release_page(),
+        "https://gitlab.com/group/sub/project/-/releases/v1.0.0"
+    );
+    assert_some_eq!(
+        parsed.commit_page(),
+        "https://gitlab.com/group/sub/project/-/commit/v1.0.0"
+    );
+    assert_some_eq!(
+        parsed.compare("v1.0.0", "main"),
+        "https://gitlab.com/group/sub/project/-/compare/v1.0.0...main"
+    );
+    // GitLab release assets are arbitrary links
+    assert_none!(parsed.release_asset("project.tar.gz"));
+
+    let parsed = HostedGitInfo::from_url("gitlab:foo/bar").unwrap();
+    assert_none!(parsed.release_page());
+    assert_none!(parsed.commit_page());
+}
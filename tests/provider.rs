@@ -0,0 +1,55 @@
+use claim::*;
+use hosted_git_info::{HostedGitInfo, ParseProviderError, Provider};
+
+#[test]
+fn all() {
+    assert_eq!(
+        Provider::all(),
+        &[
+            Provider::BitBucket,
+            Provider::Gist,
+            Provider::GitHub,
+            Provider::GitLab
+        ]
+    );
+}
+
+#[test]
+fn metadata() {
+    assert_eq!(Provider::GitHub.shortcut(), "github");
+    assert_eq!(Provider::GitHub.domain(), "github.com");
+    assert_eq!(Provider::GitHub.display_name(), "GitHub");
+    assert_eq!(Provider::GitHub.tree_path(), "tree");
+    assert_eq!(Provider::BitBucket.tree_path(), "src");
+    assert_eq!(Provider::Gist.domain(), "gist.github.com");
+    assert!(Provider::GitHub.supported_schemes().contains(&"http"));
+    assert!(!Provider::GitLab.supported_schemes().contains(&"git"));
+}
+
+#[test]
+fn supported_schemes_match_parser() {
+    for &provider in Provider::all() {
+        for scheme in &["git", "http", "https", "git+https", "ssh", "git+ssh"] {
+            let url = format!("{}://{}/foo/bar.git", scheme, provider.domain());
+            let supported = provider.supported_schemes().contains(scheme);
+            assert_eq!(
+                HostedGitInfo::from_url(&url).is_ok(),
+                supported,
+                "{} does not match supported_schemes()",
+                url
+            );
+        }
+    }
+}
+
+#[test]
+fn display_and_from_str() {
+    for &provider in Provider::all() {
+        assert_ok_eq!(provider.to_string().parse::<Provider>(), provider);
+        assert_ok_eq!(provider.domain().parse::<Provider>(), provider);
+    }
+
+    assert_ok_eq!("GitLab".parse::<Provider>(), Provider::GitLab);
+    assert_err!("sourcehut".parse::<Provider>());
+    assert_eq!("git.sr.ht".parse::<Provider>(), Err(ParseProviderError));
+}
@@ -0,0 +1,148 @@
+mod common;
+
+use claim::*;
+use common::tmp_dir;
+use hosted_git_info::{read_remotes, ConfigError, ParseError, Provider, RemoteUrlKind};
+use std::fs;
+
+const CONFIG: &str = r#"
+[core]
+	repositoryformatversion = 0
+	bare = false
+[remote "origin"]
+	url = git@github.com:foo/bar.git
+	fetch = +refs/heads/*:refs/remotes/origin/*
+	pushurl = "https://gitlab.com/foo/bar.git" ; mirror
+[remote "Upstream"]
+	URL = https://example.com/foo/bar.git # not hosted
+[branch "main"]
+	remote = origin
+"#;
+
+#[test]
+fn work_tree() {
+    let path = tmp_dir("work_tree");
+    fs::create_dir(path.join(".git")).unwrap();
+    fs::write(path.join(".git/config"), CONFIG).unwrap();
+
+    let remotes = assert_ok!(read_remotes(&path));
+    assert_eq!(remotes.len(), 3);
+
+    assert_eq!(remotes[0].name(), "origin");
+    assert_eq!(remotes[0].kind(), RemoteUrlKind::Url);
+    assert_eq!(remotes[0].url(), "git@github.com:foo/bar.git");
+    let info = assert_ok!(remotes[0].info());
+    assert_eq!(info.provider(), Provider::GitHub);
+    assert_eq!(info.user(), Some("foo"));
+    assert_eq!(info.project(), "bar");
+
+    assert_eq!(remotes[1].name(), "origin");
+    assert_eq!(remotes[1].kind(), RemoteUrlKind::PushUrl);
+    assert_eq!(remotes[1].url(), "https://gitlab.com/foo/bar.git");
+    assert_eq!(assert_ok!(remotes[1].info()).provider(), Provider::GitLab);
+
+    assert_eq!(remotes[2].name(), "Upstream");
+    assert_eq!(remotes[2].kind(), RemoteUrlKind::Url);
+    assert_eq!(remotes[2].url(), "https://example.com/foo/bar.git");
+    assert_eq!(remotes[2].info(), Err(ParseError::UnknownUrl));
+}
+
+#[test]
+fn bare_repository() {
+    let path = tmp_dir("bare_repository");
+    fs::write(path.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+    fs::write(
+        path.join("config"),
+        "[remote \"origin\"]\n\turl = gitlab:foo/bar\n",
+    )
+    .unwrap();
+
+    let remotes = assert_ok!(read_remotes(&path));
+    assert_eq!(remotes.len(), 1);
+    assert_eq!(assert_ok!(remotes[0].info()).provider(), Provider::GitLab);
+}
+
+#[test]
+fn linked_work_tree() {
+    let root = tmp_dir("linked_work_tree");
+    let main = root.join("main");
+    let linked = root.join("linked");
+    fs::create_dir_all(main.join(".git/worktrees/linked")).unwrap();
+    fs::create_dir_all(&linked).unwrap();
+    fs::write(main.join(".git/config"), CONFIG).unwrap();
+    fs::write(main.join(".git/worktrees/linked/commondir"), "../..\n").unwrap();
+    fs::write(
+        linked.join(".git"),
+        format!("gitdir: {}\n", main.join(".git/worktrees/linked").display()),
+    )
+    .unwrap();
+
+    let remotes = assert_ok!(read_remotes(&linked));
+    assert_eq!(remotes.len(), 3);
+    assert_eq!(remotes[0].url(), "git@github.com:foo/bar.git");
+}
+
+#[test]
+fn submodule() {
+    let root = tmp_dir("submodule");
+    fs::create_dir_all(root.join(".git/modules/sub")).unwrap();
+    fs::create_dir_all(root.join("sub")).unwrap();
+    fs::write(
+        root.join(".git/modules/sub/config"),
+        "[remote \"origin\"]\n\turl = https://bitbucket.org/foo/sub.git\n",
+    )
+    .unwrap();
+    fs::write(root.join("sub/.git"), "gitdir: ../.git/modules/sub\n").unwrap();
+
+    let remotes = assert_ok!(read_remotes(root.join("sub")));
+    assert_eq!(remotes.len(), 1);
+    assert_eq!(
+        assert_ok!(remotes[0].info()).provider(),
+        Provider::BitBucket
+    );
+}
+
+#[test]
+fn config_syntax() {
+    let path = tmp_dir("config_syntax");
+    fs::create_dir(path.join(".git")).unwrap();
+    fs::write(
+        path.join(".git/config"),
+        concat!(
+            "# comment\n",
+            "[remote.origin] url = \"github:foo/\\\n",
+            "bar\"\n",
+            "[remote \"with \\\"quotes\\\"\"]\n",
+            "  url=gist:abc#  trailing  \n",
+            "[remote \"empty\"]\n",
+            "  url\n",
+        ),
+    )
+    .unwrap();
+
+    let remotes = assert_ok!(read_remotes(&path));
+    assert_eq!(remotes.len(), 2);
+    assert_eq!(remotes[0].name(), "origin");
+    assert_eq!(remotes[0].url(), "github:foo/bar");
+    assert_eq!(remotes[1].name(), "with \"quotes\"");
+    assert_eq!(remotes[1].url(), "gist:abc");
+}
+
+#[test]
+fn errors() {
+    let path = tmp_dir("errors");
+    assert_matches!(read_remotes(&path), Err(ConfigError::NotARepository(_)));
+
+    fs::create_dir(path.join(".git")).unwrap();
+    assert_matches!(read_remotes(&path), Err(ConfigError::Io(_)));
+
+    fs::write(
+        path.join(".git/config"),
+        "[core]\nbare = false\n[remote \"origin\"\n",
+    )
+    .unwrap();
+    assert_matches!(read_remotes(&path), Err(ConfigError::Syntax { line: 3 }));
+
+    fs::write(path.join(".git/config"), "url = github:foo/bar\n").unwrap();
+    assert_matches!(read_remotes(&path), Err(ConfigError::Syntax { line: 1 }));
+}
@@ -0,0 +1,222 @@
+mod common;
+
+use claim::*;
+use common::tmp_dir;
+use hosted_git_info::{GitConfigFiles, ParseError, Provider, UrlRewriter};
+use std::borrow::Cow;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+fn longest_prefix_wins() {
+    let mut rewriter = UrlRewriter::new();
+    rewriter
+        .add_instead_of("git@github.com:", "https://github.com/")
+        .add_instead_of("https://gitlab.com/", "https://github.com/gitlab-mirror/")
+        .add_instead_of("ignored:", "https://");
+
+    assert_eq!(
+        rewriter.rewrite("https://github.com/foo/bar.git"),
+        "git@github.com:foo/bar.git"
+    );
+    assert_eq!(
+        rewriter.rewrite("https://github.com/gitlab-mirror/foo/bar.git"),
+        "https://gitlab.com/foo/bar.git"
+    );
+    assert_matches!(
+        rewriter.rewrite("https://bitbucket.org/foo/bar.git"),
+        Cow::Owned(_)
+    );
+    assert_matches!(
+        rewriter.rewrite("git://bitbucket.org/foo/bar.git"),
+        Cow::Borrowed("git://bitbucket.org/foo/bar.git")
+    );
+}
+
+#[test]
+fn push_instead_of() {
+    let mut rewriter = UrlRewriter::new();
+    rewriter
+        .add_instead_of("https://github.com/", "gh:")
+        .add_push_instead_of("git@github.com:", "https://github.com/");
+
+    assert_eq!(rewriter.rewrite("gh:foo/bar"), "https://github.com/foo/bar");
+    assert_eq!(
+        rewriter.rewrite_push("gh:foo/bar"),
+        "https://github.com/foo/bar"
+    );
+    assert_eq!(
+        rewriter.rewrite("https://github.com/foo/bar"),
+        "https://github.com/foo/bar"
+    );
+    assert_eq!(
+        rewriter.rewrite_push("https://github.com/foo/bar"),
+        "git@github.com:foo/bar"
+    );
+}
+
+#[test]
+fn parse() {
+    let mut rewriter = UrlRewriter::new();
+    rewriter.add_instead_of("https://gitlab.com/", "https://mirror.example.com/");
+
+    let url = rewriter.parse("https://mirror.example.com/foo/bar.git#main");
+    assert!(url.is_rewritten());
+    assert_eq!(
+        url.original(),
+        "https://mirror.example.com/foo/bar.git#main"
+    );
+    assert_eq!(url.rewritten(), "https://gitlab.com/foo/bar.git#main");
+    assert_eq!(url.original_info(), Err(ParseError::UnknownUrl));
+    let info = assert_ok!(url.info());
+    assert_eq!(info.provider(), Provider::GitLab);
+    assert_eq!(info.committish(), Some("main"));
+
+    let url = rewriter.parse("github:foo/bar");
+    assert!(!url.is_rewritten());
+    assert_eq!(url.original_info(), url.info());
+    assert_eq!(assert_ok!(url.info()).provider(), Provider::GitHub);
+}
+
+#[test]
+fn from_config_files() {
+    let root = tmp_dir("from_config_files");
+    let repository = root.join("repository");
+    fs::create_dir_all(repository.join(".git")).unwrap();
+    fs::write(
+        repository.join(".git/config"),
+        "[url \"https://bitbucket.org/\"]\n\tinsteadOf = https://github.com/bb/\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("global"),
+        concat!(
+            "[url \"git@github.com:\"]\n",
+            "\tinsteadOf = https://github.com/\n",
+            "\tpushInsteadOf = gh:\n",
+            "[include]\n",
+            "\tpath = included\n",
+        ),
+    )
+    .unwrap();
+    fs::write(
+        root.join("included"),
+        "[url \"https://gitlab.com/\"]\n\tinsteadOf = gl:\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("explicit"),
+        "[url \"https://gitlab.com/other/\"]\n\tinsteadOf = gl:mirror/\n",
+    )
+    .unwrap();
+
+    let mut files = GitConfigFiles::new();
+    files.add(root.join("global"));
+
+    let mut rewriter = assert_ok!(UrlRewriter::from_config_files(&files, Some(&repository)));
+    assert_eq!(
+        rewriter.rewrite("https://github.com/foo/bar"),
+        "git@github.com:foo/bar"
+    );
+    assert_eq!(
+        rewriter.rewrite("https://github.com/bb/foo/bar"),
+        "https://bitbucket.org/foo/bar"
+    );
+    assert_eq!(
+        rewriter.rewrite_push("gh:foo/bar"),
+        "git@github.com:foo/bar"
+    );
+    assert_eq!(rewriter.rewrite("gl:foo/bar"), "https://gitlab.com/foo/bar");
+    assert_eq!(
+        rewriter.rewrite("gl:mirror/foo/bar"),
+        "https://gitlab.com/mirror/foo/bar"
+    );
+
+    assert_ok!(rewriter.add_file(root.join("explicit")));
+    assert_eq!(
+        rewriter.rewrite("gl:mirror/foo/bar"),
+        "https://gitlab.com/other/foo/bar"
+    );
+
+    let rewriter = assert_ok!(UrlRewriter::from_config_files(&files, None::<PathBuf>));
+    assert_eq!(
+        rewriter.rewrite("https://github.com/bb/foo/bar"),
+        "git@github.com:bb/foo/bar"
+    );
+
+    assert_err!(UrlRewriter::new().add_file(root.join("missing")));
+}
+
+// `from_env` reads the environment of the process, so it runs in a child
+// process of this test binary with a controlled environment, and prints the
+// paths after a marker, because the test harness may print on the same line
+const FROM_ENV_CHILD: &str = "HOSTED_GIT_INFO_FROM_ENV_CHILD";
+
+#[test]
+fn from_env_child() {
+    if env::var_os(FROM_ENV_CHILD).is_some() {
+        for path in GitConfigFiles::from_env().paths() {
+            println!("path={}", path.display());
+        }
+    }
+}
+
+#[track_caller]
+fn paths_from_env(vars: &[(&str, &str)]) -> Vec<String> {
+    let mut command = Command::new(env::current_exe().unwrap());
+    command
+        .args(&[
+            "from_env_child",
+            "--exact",
+            "--nocapture",
+            "--test-threads=1",
+        ])
+        .env_clear()
+        .env(FROM_ENV_CHILD, "1");
+    for (key, value) in vars {
+        command.env(key, value);
+    }
+
+    let output = command.output().unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .filter_map(|line| line.split("path=").nth(1))
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn from_env() {
+    assert_eq!(
+        paths_from_env(&[("HOME", "/home/me")]),
+        [
+            "/etc/gitconfig",
+            "/home/me/.config/git/config",
+            "/home/me/.gitconfig"
+        ]
+    );
+    assert_eq!(
+        paths_from_env(&[
+            ("HOME", "/home/me"),
+            ("XDG_CONFIG_HOME", "/xdg"),
+            ("GIT_CONFIG_SYSTEM", "/system"),
+        ]),
+        ["/system", "/xdg/git/config", "/home/me/.gitconfig"]
+    );
+    assert_eq!(
+        paths_from_env(&[
+            ("HOME", "/home/me"),
+            ("GIT_CONFIG_NOSYSTEM", "true"),
+            ("GIT_CONFIG_GLOBAL", "/global"),
+        ]),
+        ["/global"]
+    );
+    assert_eq!(
+        paths_from_env(&[("GIT_CONFIG_NOSYSTEM", "0")]),
+        ["/etc/gitconfig"]
+    );
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use claim::*;
+use common::tmp_dir;
+use hosted_git_info::{ConfigError, ParseError, Provider, SshConfig};
+use std::fs;
+
+const CONFIG: &str = r#"
+# personal account
+Host github-work gh-*
+    HostName github.com
+    User git
+    IdentityFile ~/.ssh/id_work
+
+Host *.internal !legacy.internal
+    HostName=%h.example.com
+    Port 2222
+
+Host gitlab-mirror
+    HostName "gitlab.com"
+
+Match host foo
+    HostName bitbucket.org
+
+Host *
+    User fallback
+    Port 22
+"#;
+
+#[test]
+fn resolve() {
+    let config = assert_ok!(SshConfig::parse(CONFIG));
+
+    let host = config.resolve("github-work");
+    assert_eq!(host.alias(), "github-work");
+    assert_eq!(host.host_name(), "github.com");
+    assert_eq!(host.user(), Some("git"));
+    assert_eq!(host.port(), Some(22));
+
+    let host = config.resolve("GH-Personal");
+    assert_eq!(host.host_name(), "github.com");
+
+    let host = config.resolve("git.internal");
+    assert_eq!(host.host_name(), "git.internal.example.com");
+    assert_eq!(host.user(), Some("fallback"));
+    assert_eq!(host.port(), Some(2222));
+
+    let host = config.resolve("legacy.internal");
+    assert_eq!(host.host_name(), "legacy.internal");
+    assert_eq!(host.port(), Some(22));
+
+    let host = config.resolve("foo");
+    assert_eq!(host.host_name(), "foo");
+}
+
+#[test]
+fn parse_url() {
+    let config = assert_ok!(SshConfig::parse(CONFIG));
+
+    let url = assert_ok!(config.parse_url("git@github-work:org/repo.git#main"));
+    assert_eq!(url.alias(), Some("github-work"));
+    assert_eq!(assert_some!(url.host()).host_name(), "github.com");
+    let info = url.info();
+    assert_eq!(info.provider(), Provider::GitHub);
+    assert_eq!(info.user(), Some("org"));
+    assert_eq!(info.project(), "repo");
+    assert_eq!(info.committish(), Some("main"));
+    assert_eq!(url.ssh(), "git@github-work:org/repo.git#main");
+
+    let url = assert_ok!(config.parse_url("ssh://git@gitlab-mirror/group/sub/repo.git"));
+    assert_eq!(url.alias(), Some("gitlab-mirror"));
+    assert_eq!(url.info().provider(), Provider::GitLab);
+    assert_eq!(url.info().user(), Some("group/sub"));
+    assert_eq!(url.ssh(), "git@gitlab-mirror:group/sub/repo.git");
+
+    // the user in the URL takes precedence over the `User` option
+    let url = assert_ok!(config.parse_url("me@github-work:org/repo.git"));
+    assert_some_eq!(url.user(), "me");
+    assert_eq!(url.ssh(), "me@github-work:org/repo.git");
+    let url = assert_ok!(config.parse_url("ssh://gitlab-mirror/group/sub/repo.git"));
+    assert_some_eq!(url.user(), "fallback");
+    assert_eq!(url.ssh(), "fallback@gitlab-mirror:group/sub/repo.git");
+
+    // the directory and an explicit port are kept
+    let url = assert_ok!(config.parse_url("ssh://git@github-work/org/repo.git#main::path:pkg"));
+    assert_some_eq!(url.info().directory(), "pkg");
+    assert_eq!(url.ssh(), "git@github-work:org/repo.git#main::path:pkg");
+    let url = assert_ok!(config.parse_url("ssh://git@github-work:2222/org/repo.git"));
+    assert_eq!(url.ssh(), "git+ssh://git@github-work:2222/org/repo.git");
+
+    // known hosts and non-SSH URLs are not resolved
+    let url = assert_ok!(config.parse_url("git@github.com:org/repo.git"));
+    assert_none!(url.alias());
+    assert_none!(url.user());
+    assert_eq!(url.ssh(), "git@github.com:org/repo.git");
+    let url = assert_ok!(config.parse_url("org/repo"));
+    assert_none!(url.alias());
+    assert_eq!(
+        config.parse_url("https://github-work/org/repo.git"),
+        Err(ParseError::UnknownUrl)
+    );
+
+    assert_eq!(
+        config.parse_url("git@unknown:org/repo.git"),
+        Err(ParseError::UnknownUrl)
+    );
+}
+
+#[test]
+fn include() {
+    let dir = tmp_dir("ssh_include");
+    fs::create_dir_all(dir.join("config.d")).unwrap();
+    fs::write(
+        dir.join("config"),
+        "Include config.d/*.conf\nHost *\n  HostName fallback.example.com\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("config.d/work.conf"),
+        "Host github-work\n  HostName github.com\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("config.d/ignored.txt"),
+        "Host *\n  HostName ignored\n",
+    )
+    .unwrap();
+
+    let config = assert_ok!(SshConfig::from_file(dir.join("config")));
+    assert_eq!(config.resolve("github-work").host_name(), "github.com");
+    assert_eq!(config.resolve("other").host_name(), "fallback.example.com");
+
+    fs::write(dir.join("loop"), "Include loop\n").unwrap();
+    assert_matches!(
+        SshConfig::from_file(dir.join("loop")),
+        Err(ConfigError::IncludeDepth(_))
+    );
+}
+
+#[test]
+fn errors() {
+    assert_matches!(
+        SshConfig::parse("Host foo\n  HostName \"unterminated\n"),
+        Err(ConfigError::Syntax { line: 2 })
+    );
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use claim::*;
+use common::tmp_dir;
+use hosted_git_info::{parse_submodules, read_submodules, ConfigError, ParseError, Provider};
+use std::fs;
+
+const GITMODULES: &str = r#"
+[submodule "absolute"]
+	path = vendor/absolute
+	url = https://github.com/foo/absolute.git
+	branch = main
+[submodule "sibling"]
+	path = vendor/sibling
+	url = ../sibling.git
+[submodule "nested"]
+	path = vendor/nested
+	url = ../../other/nested
+[submodule "unknown"]
+	path = vendor/unknown
+	url = https://example.com/foo/unknown.git
+[submodule "missing"]
+	path = vendor/missing
+[submodule "absolute"]
+	branch = stable
+"#;
+
+#[test]
+fn https_superproject() {
+    let submodules = assert_ok!(parse_submodules(
+        GITMODULES,
+        Some("https://gitlab.com/group/sub/main.git/")
+    ));
+    assert_eq!(submodules.len(), 5);
+
+    let absolute = &submodules[0];
+    assert_eq!(absolute.name(), "absolute");
+    assert_eq!(absolute.path(), Some("vendor/absolute"));
+    assert_eq!(absolute.branch(), Some("stable"));
+    assert_eq!(absolute.url(), Some("https://github.com/foo/absolute.git"));
+    assert_eq!(absolute.resolved_url(), absolute.url());
+    let info = assert_ok!(absolute.info());
+    assert_eq!(info.provider(), Provider::GitHub);
+    assert_eq!(info.user(), Some("foo"));
+    assert_eq!(info.project(), "absolute");
+
+    let sibling = &submodules[1];
+    assert_eq!(sibling.url(), Some("../sibling.git"));
+    assert_eq!(
+        sibling.resolved_url(),
+        Some("https://gitlab.com/group/sub/sibling.git")
+    );
+    let info = assert_ok!(sibling.info());
+    assert_eq!(info.provider(), Provider::GitLab);
+    assert_eq!(info.user(), Some("group/sub"));
+    assert_eq!(info.project(), "sibling");
+
+    let nested = &submodules[2];
+    assert_eq!(
+        nested.resolved_url(),
+        Some("https://gitlab.com/group/other/nested")
+    );
+    assert_eq!(assert_ok!(nested.info()).user(), Some("group/other"));
+
+    let unknown = &submodules[3];
+    assert_eq!(unknown.info(), Err(ParseError::UnknownUrl));
+
+    let missing = &submodules[4];
+    assert_none!(missing.url());
+    assert_none!(missing.resolved_url());
+    assert_eq!(missing.info(), Err(ParseError::UnknownUrl));
+}
+
+#[test]
+fn scp_superproject() {
+    let submodules = assert_ok!(parse_submodules(
+        GITMODULES,
+        Some("git@github.com:main.git")
+    ));
+
+    assert_eq!(
+        submodules[1].resolved_url(),
+        Some("git@github.com:sibling.git")
+    );
+    assert_none!(submodules[2].resolved_url());
+
+    let submodules = assert_ok!(parse_submodules(
+        GITMODULES,
+        Some("git@bitbucket.org:foo/main.git")
+    ));
+    assert_eq!(
+        submodules[1].resolved_url(),
+        Some("git@bitbucket.org:foo/sibling.git")
+    );
+    assert_eq!(
+        assert_ok!(submodules[1].info()).provider(),
+        Provider::BitBucket
+    );
+}
+
+#[test]
+fn without_superproject() {
+    let submodules = assert_ok!(parse_submodules(GITMODULES, None));
+    assert_ok!(submodules[0].info());
+    assert_none!(submodules[1].resolved_url());
+    assert_eq!(submodules[1].info(), Err(ParseError::UnknownUrl));
+}
+
+#[test]
+fn read() {
+    let path = tmp_dir("read_submodules");
+
+    assert_matches!(read_submodules(&path, None), Err(ConfigError::Io(_)));
+
+    fs::write(path.join(".gitmodules"), GITMODULES).unwrap();
+    let submodules = assert_ok!(read_submodules(&path, None));
+    assert_eq!(submodules.len(), 5);
+
+    fs::write(path.join(".gitmodules"), "[submodule \"foo\"\n").unwrap();
+    assert_matches!(
+        read_submodules(&path, None),
+        Err(ConfigError::Syntax { line: 1 })
+    );
+}